@@ -1,129 +1,1966 @@
 #![no_std]
 use common::{
-    cleanup_operation, ensure_not_replayed, is_operation_executed, BetPlacedEvent, ContractError,
-    SpinExecutedEvent,
+    cleanup_operation, create_bet_event, create_spin_reward_event, ensure_not_paused,
+    ensure_not_replayed, extend_instance, extend_persistent, is_operation_executed,
+    BetCancelledEvent, BetOutcome, BetResettledEvent, BetStatus, ContractError, MatchSettledEvent,
+    SettlementEvent, SpinExecutedEvent, WinningsAvailableEvent, BET_CANCELLED_EVENT, BET_EVENT,
+    BET_RESETTLED_EVENT, MATCH_SETTLED_EVENT, SETTLEMENT_EVENT, SPIN_EXECUTED_EVENT,
+    SPIN_REWARD_EVENT, WINNINGS_AVAILABLE_EVENT,
 };
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, BytesN, Env, Map, Symbol};
+#[cfg(feature = "legacy-bet-event")]
+use common::{BetPlacedEvent, BET_PLACED_EVENT};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal,
+    Map, String, Symbol, Val, Vec, U256,
+};
+
+/// Current storage layout version.
+///
+/// * `1` - all spin executions kept in a single `DataKey::SpinExecutions` map.
+/// * `2` - one `DataKey::SpinExecution` entry per spin plus an append-only index.
+/// * `3` - bets stored per `(match, bettor, index)` with their token, status and metadata.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Release of the contract code, bumped whenever a new wasm is shipped.
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Upper bound on the number of entries returned by paginated reads.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Odds are a payout multiplier in basis points (10000 == 1.0x).
+pub const ODDS_SCALE: i128 = 10_000;
+
+/// House fees are in basis points of the wager (10000 == 100%).
+pub const FEE_SCALE: i128 = 10_000;
+
+/// Length of the rolling window the per-bettor daily limit applies to.
+pub const DAILY_WINDOW_SECONDS: u64 = 86_400;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpinExecution {
+    pub spin_id: BytesN<32>,
+    pub executor: Address,
+    pub timestamp: u64,
+}
+
+/// Payout attached to a spin by the backend.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpinReward {
+    pub token_address: Address,
+    pub reward_amount: i128,
+    pub multiplier: u32,
+}
+
+/// Every contract-wide setting, bundled so clients can read them in one call.
+/// Limits that were never set are `None`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub treasury: Address,
+    pub fee_bps: u32,
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+    pub max_odds: Option<u32>,
+    pub daily_limit: Option<i128>,
+    pub global_exposure_cap: Option<i128>,
+    pub prevent_double_betting: bool,
+    pub house_counterparty: bool,
+    pub paused: bool,
+    pub global_pauser: Option<Address>,
+    pub test_mode: bool,
+}
+
+/// A fixture bets can be placed on. Betting closes at `start_time`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub match_id: BytesN<32>,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bet {
+    pub bettor: Address,
+    pub token_address: Address,
+    pub amount: i128,
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32,
+    pub timestamp: u64,
+    pub status: BetStatus,
+    pub metadata: Map<Symbol, String>, // e.g. selection name or market type
+}
+
+/// Bet layout from before schema v3, stored under `legacy_bet_key`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetV1 {
+    pub bettor: Address,
+    pub amount: i128,
+    pub match_id: BytesN<32>,
+    pub bet_type: Symbol,
+    pub odds: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,            // Address: changes settings, settles matches and moves funds
+    BackendSigner,    // Address: co-signs spin executions
+    BackendPublicKey, // ed25519 key the backend signs spins with
+    UsedSpinHashes,
+    SpinExecutions, // Legacy (schema v1) map of all spin executions, drained by `migrate`
+    SpinExecution(BytesN<32>),
+    SpinCount,
+    SpinAt(u32),                   // Append-only index position -> spin id
+    Nonce(Address),                // u64: next spin nonce expected from an executor
+    Bet(BytesN<32>, Address, u32), // (match, bettor, index) -> Bet
+    BetCount(BytesN<32>, Address), // u32: bets placed by a bettor on a match
+    PreventDoubleBetting,
+    SchemaVersion,
+    Blocked(Address),
+    GlobalExposure,           // i128: potential payout liability across all open bets
+    GlobalExposureCap,        // i128: maximum allowed GlobalExposure
+    TokenExposure(Address),   // i128: potential payout liability of open bets in a token
+    HouseCounterparty,        // bool: require contract liquidity to cover payouts at place_bet
+    TestMode,                 // bool: set once at init, relaxes backend signer auth
+    Paused,                   // bool: local incident switch
+    GlobalPauser,             // Address: contract consulted via `is_paused` before mutations
+    MatchBettors(BytesN<32>), // Vec<Address>: every bettor with a bet on the match
+    MatchVolume(BytesN<32>),  // i128: stake currently wagered on the match (net of cancellations)
+    BetLimits,                // (i128, i128): inclusive min and max stake per bet
+    Match(BytesN<32>),        // Match: registered fixture
+    MaxOdds,                  // u32: highest odds accepted by place_bet, in ODDS_SCALE units
+    PendingReward(Address, Address), // i128: spin rewards owed to an executor in a token
+    DailyLimit,               // i128: most a bettor may stake per DAILY_WINDOW_SECONDS
+    DailyWagered(Address),    // (u64, i128): bettor's current window start and stake within it
+    BettorMatches(Address),   // Vec<BytesN<32>>: every match the bettor has bet on
+    Claimable(Address, Address), // i128: settled winnings a bettor can claim in a token
+    TotalClaimable(Address),  // i128: unclaimed winnings owed across all bettors in a token
+    Initialized,              // bool: set by initialize, which can only run once
+    EscrowBalance(Address), // i128: funds deposited into the contract in a token and not yet paid out
+    TotalBettors,           // u32: distinct addresses that have ever placed a bet
+    KnownBettor(Address),   // bool: the address has been counted in TotalBettors
+    FeeBps,                 // u32: house fee taken from each wager, in FEE_SCALE units
+    Treasury,               // Address: receives house fees, defaults to the admin
+    ExecutorSpins(Address), // Vec<BytesN<32>>: spin ids executed by an address, oldest first
+    OpenStakes(Address),    // i128: stakes of active bets escrowed in a token
+    CancelTiers,            // Vec<(u64, u32)>: (seconds before start, penalty in FEE_SCALE units)
+    BetSettlement(BytesN<32>, Address, u32), // (BetOutcome, i128, u32): outcome, payout and ClaimCount when settled
+    ClaimCount(Address, Address),            // u32: claims a bettor has made in a token
+    ClaimInterval,                           // u64: minimum seconds between a user's claims
+    LastClaimAt(Address),                    // u64: timestamp of the user's last claim
+}
+
+/// Write a persistent entry and extend its TTL so it isn't archived.
+fn set_persistent<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    extend_persistent(env, key);
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+    // Deployments that predate the admin role were administered by the backend signer
+    let storage = env.storage().persistent();
+    let stored_admin: Address = storage
+        .get(&DataKey::Admin)
+        .or_else(|| storage.get(&DataKey::BackendSigner))
+        .ok_or(ContractError::Unauthorized)?;
+
+    admin.require_auth();
+    if *admin != stored_admin {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_backend_signer(env: &Env, signer: &Address) -> Result<(), ContractError> {
+    let backend_signer: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::BackendSigner)
+        .ok_or(ContractError::Unauthorized)?;
+
+    signer.require_auth();
+    if *signer != backend_signer {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn ensure_not_blocked(env: &Env, addr: &Address) -> Result<(), ContractError> {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Blocked(addr.clone()))
+    {
+        return Err(ContractError::AddressBlocked);
+    }
+    Ok(())
+}
+
+fn ensure_active(env: &Env) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    ensure_not_paused(
+        env,
+        storage.get(&DataKey::Paused).unwrap_or(false),
+        storage.get(&DataKey::GlobalPauser),
+    )
+}
+
+/// Gross amount returned to the bettor if the bet wins (stake included).
+fn potential_payout(amount: i128, odds: u32) -> Result<i128, ContractError> {
+    amount
+        .checked_mul(odds as i128)
+        .map(|scaled| scaled / ODDS_SCALE)
+        .ok_or(ContractError::ArithmeticOverflow)
+}
+
+/// Only active bets can be settled or cancelled.
+fn ensure_active_bet(bet: &Bet) -> Result<(), ContractError> {
+    match bet.status {
+        BetStatus::Settled => Err(ContractError::BetAlreadySettled),
+        BetStatus::Cancelled => Err(ContractError::InvalidStatus),
+        _ => Ok(()),
+    }
+}
+
+fn bet_count(env: &Env, match_id: &BytesN<32>, bettor: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BetCount(match_id.clone(), bettor.clone()))
+        .unwrap_or(0)
+}
+
+/// Every bet `bettor` has placed on `match_id`, oldest first.
+fn load_bets(env: &Env, match_id: &BytesN<32>, bettor: &Address) -> Vec<Bet> {
+    let storage = env.storage().persistent();
+    let mut bets = Vec::new(env);
+    for index in 0..bet_count(env, match_id, bettor) {
+        if let Some(bet) = storage.get(&DataKey::Bet(match_id.clone(), bettor.clone(), index)) {
+            bets.push_back(bet);
+        }
+    }
+    bets
+}
+
+fn ensure_match_ended(env: &Env, match_id: &BytesN<32>) -> Result<(), ContractError> {
+    let fixture: Match = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Match(match_id.clone()))
+        .ok_or(ContractError::MatchNotFound)?;
+    if env.ledger().timestamp() < fixture.end_time {
+        return Err(ContractError::MatchNotEnded);
+    }
+    Ok(())
+}
+
+fn settlement_type(outcome: &BetOutcome) -> &'static str {
+    match outcome {
+        BetOutcome::Win => "WIN",
+        BetOutcome::Lose => "LOSE",
+        BetOutcome::Draw => "DRAW",
+    }
+}
+
+/// Totals for the bets closed by one settlement pass.
+#[derive(Default)]
+struct Settled {
+    count: u32,
+    staked: i128,
+    payout: i128,
+}
+
+/// Settle all of `bettor`'s active bets on `match_id`, paying each out in the
+/// token it was escrowed in. Bets that are already closed are skipped.
+fn settle_bettor(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bettor: &Address,
+    outcome: &BetOutcome,
+) -> Result<Settled, ContractError> {
+    let storage = env.storage().persistent();
+    let mut settled = Settled::default();
+    for index in 0..bet_count(env, match_id, bettor) {
+        let bet_key = DataKey::Bet(match_id.clone(), bettor.clone(), index);
+        let mut bet: Bet = storage.get(&bet_key).ok_or(ContractError::BetNotFound)?;
+        if ensure_active_bet(&bet).is_err() {
+            continue;
+        }
+
+        let potential = potential_payout(bet.amount, bet.odds)?;
+        let payout = match outcome {
+            BetOutcome::Win => potential,
+            BetOutcome::Lose => 0,
+            BetOutcome::Draw => bet.amount,
+        };
+
+        bet.status = BetStatus::Settled;
+        set_persistent(env, &bet_key, &bet);
+        // Kept so `resettle_bet` can tell whether this payout was claimed
+        let claims: u32 = storage
+            .get(&DataKey::ClaimCount(
+                bettor.clone(),
+                bet.token_address.clone(),
+            ))
+            .unwrap_or(0);
+        set_persistent(
+            env,
+            &DataKey::BetSettlement(match_id.clone(), bettor.clone(), index),
+            &(*outcome, payout, claims),
+        );
+        release_exposure(env, &bet.token_address, potential);
+        release_stake(env, &bet.token_address, bet.amount);
+
+        settled.count += 1;
+        settled.staked = settled
+            .staked
+            .checked_add(bet.amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        settled.payout = settled
+            .payout
+            .checked_add(payout)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+
+        let event = SettlementEvent {
+            bet_id: U256::from_be_bytes(env, &Bytes::from(match_id.clone())),
+            winner: bettor.clone(),
+            payout,
+            betting_contract: env.current_contract_address(),
+            timestamp: env.ledger().timestamp(),
+            settlement_type: Symbol::new(env, settlement_type(outcome)),
+            final_odds: bet.odds,
+            metadata: Map::new(env),
+        };
+        env.events()
+            .publish((SETTLEMENT_EVENT, match_id.clone()), event);
+
+        // Always pay out in the token that was escrowed
+        if payout > 0 {
+            credit_winnings(env, bettor, &bet.token_address, match_id, payout)?;
+        }
+    }
+    Ok(settled)
+}
+
+/// Owe `amount` of `token_address` to `bettor` until they `claim` it.
+fn credit_winnings(
+    env: &Env,
+    bettor: &Address,
+    token_address: &Address,
+    match_id: &BytesN<32>,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let claimable_key = DataKey::Claimable(bettor.clone(), token_address.clone());
+    let claimable: i128 = storage.get(&claimable_key).unwrap_or(0);
+    let total_key = DataKey::TotalClaimable(token_address.clone());
+    let total: i128 = storage.get(&total_key).unwrap_or(0);
+    set_persistent(
+        env,
+        &claimable_key,
+        &claimable
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?,
+    );
+    set_persistent(
+        env,
+        &total_key,
+        &total
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?,
+    );
+
+    let event = WinningsAvailableEvent {
+        bettor: bettor.clone(),
+        match_id: match_id.clone(),
+        amount,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events()
+        .publish((WINNINGS_AVAILABLE_EVENT, bettor.clone()), event);
+    Ok(())
+}
+
+/// Decimal form of a non-negative token amount.
+fn amount_to_string(env: &Env, value: i128) -> String {
+    let mut digits = [0u8; 39];
+    let mut start = digits.len();
+    let mut remaining = value.unsigned_abs();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+    String::from_str(env, core::str::from_utf8(&digits[start..]).unwrap())
+}
+
+/// Split a wager into the stake to escrow and the house fee.
+fn split_fee(env: &Env, amount: i128) -> Result<(i128, i128), ContractError> {
+    let fee_bps: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::FeeBps)
+        .unwrap_or(0);
+    let fee = amount
+        .checked_mul(fee_bps as i128)
+        .map(|scaled| scaled / FEE_SCALE)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    Ok((amount - fee, fee))
+}
+
+/// Send `fee` of `token_address` from `bettor` to the treasury.
+fn collect_fee(
+    env: &Env,
+    bettor: &Address,
+    token_address: &Address,
+    fee: i128,
+) -> Result<(), ContractError> {
+    if fee <= 0 {
+        return Ok(());
+    }
+    let treasury = fee_treasury(env).ok_or(ContractError::NotInitialized)?;
+    token::Client::new(env, token_address).transfer(bettor, &treasury, &fee);
+    Ok(())
+}
+
+/// Where house fees go: the configured treasury, else the admin.
+fn fee_treasury(env: &Env) -> Option<Address> {
+    let storage = env.storage().persistent();
+    storage
+        .get(&DataKey::Treasury)
+        .or_else(|| storage.get(&DataKey::Admin))
+        .or_else(|| storage.get(&DataKey::BackendSigner))
+}
+
+/// Stake, fixture, odds and size checks that don't depend on the bettor's
+/// other bets.
+fn check_bet(
+    env: &Env,
+    amount: i128,
+    match_id: &BytesN<32>,
+    odds: u32,
+) -> Result<(), ContractError> {
+    if amount <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let storage = env.storage().persistent();
+
+    let fixture: Match = storage
+        .get(&DataKey::Match(match_id.clone()))
+        .ok_or(ContractError::MatchNotFound)?;
+    if env.ledger().timestamp() >= fixture.start_time {
+        return Err(ContractError::MatchClosed);
+    }
+
+    if odds == 0 {
+        return Err(ContractError::InvalidOdds);
+    }
+    if let Some(max_odds) = storage.get::<_, u32>(&DataKey::MaxOdds) {
+        if odds > max_odds {
+            return Err(ContractError::InvalidOdds);
+        }
+    }
+
+    if let Some((min_amount, max_amount)) = storage.get::<_, (i128, i128)>(&DataKey::BetLimits) {
+        if amount < min_amount {
+            return Err(ContractError::BetBelowMinimum);
+        }
+        if amount > max_amount {
+            return Err(ContractError::BetAboveMaximum);
+        }
+    }
+
+    Ok(())
+}
+
+/// Enforce the bettor- and house-wide limits and record a bet that passed
+/// `check_bet`. `amount` is the stake after the house fee and `wager` the
+/// gross amount, which counts towards the daily limit like the per-bet limits.
+/// `incoming` is the stake the caller is about to escrow in this call,
+/// including this bet, which counts towards house liquidity.
+#[allow(clippy::too_many_arguments)]
+fn record_bet(
+    env: &Env,
+    bettor: &Address,
+    token_address: &Address,
+    amount: i128,
+    wager: i128,
+    match_id: BytesN<32>,
+    bet_type: Symbol,
+    odds: u32,
+    metadata: Map<Symbol, String>,
+    incoming: i128,
+) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+
+    // Responsible-gambling cap: a window opens with the first bet after the
+    // previous one lapsed, and every stake in it counts towards the limit
+    let now = env.ledger().timestamp();
+    let wagered_key = DataKey::DailyWagered(bettor.clone());
+    let (mut window_start, mut wagered): (u64, i128) =
+        storage.get(&wagered_key).unwrap_or((now, 0));
+    if now.saturating_sub(window_start) >= DAILY_WINDOW_SECONDS {
+        window_start = now;
+        wagered = 0;
+    }
+    wagered = wagered
+        .checked_add(wager)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    if let Some(daily_limit) = storage.get::<_, i128>(&DataKey::DailyLimit) {
+        if wagered > daily_limit {
+            return Err(ContractError::DailyLimitExceeded);
+        }
+    }
+
+    // Check if double betting is prevented
+    let prevent_double: bool = storage.get(&DataKey::PreventDoubleBetting).unwrap_or(false);
+    if prevent_double
+        && load_bets(env, &match_id, bettor)
+            .iter()
+            .any(|bet| bet.status != BetStatus::Cancelled)
+    {
+        return Err(ContractError::BetAlreadyPlaced);
+    }
+
+    let payout = potential_payout(amount, odds)?;
+
+    // House-wide solvency guard across every open bet
+    let exposure: i128 = storage.get(&DataKey::GlobalExposure).unwrap_or(0);
+    let new_exposure = exposure
+        .checked_add(payout)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    if let Some(cap) = storage.get::<_, i128>(&DataKey::GlobalExposureCap) {
+        if new_exposure > cap {
+            return Err(ContractError::ExposureLimitExceeded);
+        }
+    }
+
+    let token_client = token::Client::new(env, token_address);
+    let token_exposure_key = DataKey::TokenExposure(token_address.clone());
+    let token_exposure: i128 = storage.get(&token_exposure_key).unwrap_or(0);
+    let new_token_exposure = token_exposure
+        .checked_add(payout)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+
+    let open_stakes_key = DataKey::OpenStakes(token_address.clone());
+    let open_stakes: i128 = storage.get(&open_stakes_key).unwrap_or(0);
+    let new_open_stakes = open_stakes
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+
+    // With the house as counterparty, its own funds in this token must cover
+    // every open bet's winnings beyond the stake. The contract's holdings
+    // (including the stake this call escrows) are only the house's once
+    // unclaimed winnings and open stakes are set aside.
+    let house_counterparty: bool = storage.get(&DataKey::HouseCounterparty).unwrap_or(false);
+    if house_counterparty {
+        let owed: i128 = storage
+            .get(&DataKey::TotalClaimable(token_address.clone()))
+            .unwrap_or(0);
+        let free_liquidity = token_client
+            .balance(&env.current_contract_address())
+            .checked_add(incoming)
+            .and_then(|held| held.checked_sub(owed))
+            .and_then(|held| held.checked_sub(new_open_stakes))
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        if free_liquidity < new_token_exposure - new_open_stakes {
+            return Err(ContractError::InsufficientBalance);
+        }
+    }
+
+    let volume_key = DataKey::MatchVolume(match_id.clone());
+    let volume: i128 = storage.get(&volume_key).unwrap_or(0);
+    let new_volume = volume
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+
+    // Checks are done: record the bet in full before the caller pulls the
+    // stake, so a reentrant token contract only ever sees settled state.
+    // If the transfer traps, the host rolls all of these writes back.
+    set_persistent(env, &DataKey::GlobalExposure, &new_exposure);
+    set_persistent(env, &token_exposure_key, &new_token_exposure);
+    set_persistent(env, &open_stakes_key, &new_open_stakes);
+    set_persistent(env, &wagered_key, &(window_start, wagered));
+
+    let bet = Bet {
+        bettor: bettor.clone(),
+        token_address: token_address.clone(),
+        amount,
+        match_id: match_id.clone(),
+        bet_type: bet_type.clone(),
+        odds,
+        timestamp: now,
+        status: BetStatus::Active,
+        metadata: metadata.clone(),
+    };
+    store_bet(env, &bet);
+
+    set_persistent(env, &volume_key, &new_volume);
+
+    // Deployments built for older indexers also publish the original payload
+    #[cfg(feature = "legacy-bet-event")]
+    env.events().publish(
+        (BET_PLACED_EVENT, match_id.clone()),
+        BetPlacedEvent {
+            bettor: bettor.clone(),
+            bet_id: Symbol::new(env, "bet"),
+            amount,
+        },
+    );
+
+    // bet_id matches the one settlement events carry for this match
+    let mut event = create_bet_event(
+        env,
+        bettor.clone(),
+        amount,
+        U256::from_be_bytes(env, &Bytes::from(match_id.clone())),
+        env.current_contract_address(),
+        bet_type,
+        odds,
+    );
+    event.metadata = metadata;
+    env.events().publish((BET_EVENT, match_id.clone()), event);
+
+    Ok(())
+}
+
+/// Save `bet` in the bettor's next slot on its match and index it for
+/// settlement and lookups.
+fn store_bet(env: &Env, bet: &Bet) {
+    let storage = env.storage().persistent();
+    let match_id = &bet.match_id;
+    let bettor = &bet.bettor;
+
+    // Each bet gets its own slot so repeat bets never overwrite escrow
+    let index = bet_count(env, match_id, bettor);
+    storage.set(&DataKey::Bet(match_id.clone(), bettor.clone(), index), bet);
+    storage.set(
+        &DataKey::BetCount(match_id.clone(), bettor.clone()),
+        &(index + 1),
+    );
+
+    let bettors_key = DataKey::MatchBettors(match_id.clone());
+    let mut bettors: Vec<Address> = storage.get(&bettors_key).unwrap_or_else(|| Vec::new(env));
+    if !bettors.contains(bettor) {
+        bettors.push_back(bettor.clone());
+        storage.set(&bettors_key, &bettors);
+    }
+
+    let known_key = DataKey::KnownBettor(bettor.clone());
+    if !storage.has(&known_key) {
+        let total: u32 = storage.get(&DataKey::TotalBettors).unwrap_or(0);
+        set_persistent(env, &DataKey::TotalBettors, &(total + 1));
+        set_persistent(env, &known_key, &true);
+    }
+
+    let matches_key = DataKey::BettorMatches(bettor.clone());
+    let mut matches: Vec<BytesN<32>> = storage.get(&matches_key).unwrap_or_else(|| Vec::new(env));
+    if !matches.contains(match_id) {
+        matches.push_back(match_id.clone());
+        storage.set(&matches_key, &matches);
+    }
+
+    // Keep the bet and what settlement reads alongside it from being archived
+    for key in [
+        DataKey::Bet(match_id.clone(), bettor.clone(), index),
+        DataKey::BetCount(match_id.clone(), bettor.clone()),
+        bettors_key,
+        matches_key,
+    ] {
+        extend_persistent(env, &key);
+    }
+    // Migrated bets may predate their fixture being registered
+    let match_key = DataKey::Match(match_id.clone());
+    if storage.has(&match_key) {
+        extend_persistent(env, &match_key);
+    }
+}
+
+/// Key a pre-v3 deployment stored a bettor's single bet on a match under,
+/// i.e. the old two-field `DataKey::Bet(match_id, bettor)`.
+fn legacy_bet_key(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bettor: &Address,
+) -> (Symbol, BytesN<32>, Address) {
+    (Symbol::new(env, "Bet"), match_id.clone(), bettor.clone())
+}
+
+/// Move a pre-v3 bet into the current layout as an active bet in
+/// `token_address`, counting its stake into escrow, volume and exposure.
+fn migrate_legacy_bet(
+    env: &Env,
+    match_id: &BytesN<32>,
+    bettor: &Address,
+    token_address: &Address,
+) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let legacy_key = legacy_bet_key(env, match_id, bettor);
+    let Some(legacy) = storage.get::<_, BetV1>(&legacy_key) else {
+        return Ok(());
+    };
+
+    let bet = Bet {
+        bettor: legacy.bettor,
+        token_address: token_address.clone(),
+        amount: legacy.amount,
+        match_id: legacy.match_id,
+        bet_type: legacy.bet_type,
+        odds: legacy.odds,
+        timestamp: legacy.timestamp,
+        status: BetStatus::Active,
+        metadata: Map::new(env),
+    };
+    let payout = potential_payout(bet.amount, bet.odds)?;
+    for (key, liability) in [
+        (DataKey::GlobalExposure, payout),
+        (DataKey::TokenExposure(token_address.clone()), payout),
+        (DataKey::OpenStakes(token_address.clone()), bet.amount),
+    ] {
+        let total: i128 = storage.get(&key).unwrap_or(0);
+        let total = total
+            .checked_add(liability)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        set_persistent(env, &key, &total);
+    }
+    let volume_key = DataKey::MatchVolume(bet.match_id.clone());
+    let volume: i128 = storage.get(&volume_key).unwrap_or(0);
+    let volume = volume
+        .checked_add(bet.amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    set_persistent(env, &volume_key, &volume);
+    credit_escrow(env, token_address, bet.amount)?;
+
+    store_bet(env, &bet);
+    storage.remove(&legacy_key);
+    Ok(())
+}
+
+/// Record `amount` of `token_address` as taken into the contract.
+fn credit_escrow(env: &Env, token_address: &Address, amount: i128) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let key = DataKey::EscrowBalance(token_address.clone());
+    let balance: i128 = storage.get(&key).unwrap_or(0);
+    let balance = balance
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    set_persistent(env, &key, &balance);
+    Ok(())
+}
+
+/// Record `amount` of `token_address` as leaving the contract. Fails rather
+/// than pay out more than was ever deposited, whatever else the contract holds.
+fn debit_escrow(env: &Env, token_address: &Address, amount: i128) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let key = DataKey::EscrowBalance(token_address.clone());
+    let balance: i128 = storage.get(&key).unwrap_or(0);
+    if amount > balance {
+        return Err(ContractError::InsufficientBalance);
+    }
+    set_persistent(env, &key, &(balance - amount));
+    Ok(())
+}
+
+/// Like `debit_escrow`, but leaves in place what the contract owes bettors:
+/// unclaimed winnings and the stakes of active bets.
+fn debit_free_escrow(
+    env: &Env,
+    token_address: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let owed: i128 = storage
+        .get(&DataKey::TotalClaimable(token_address.clone()))
+        .unwrap_or(0);
+    let open_stakes: i128 = storage
+        .get(&DataKey::OpenStakes(token_address.clone()))
+        .unwrap_or(0);
+    let escrow: i128 = storage
+        .get(&DataKey::EscrowBalance(token_address.clone()))
+        .unwrap_or(0);
+    if amount > escrow - owed - open_stakes {
+        return Err(ContractError::InsufficientBalance);
+    }
+    debit_escrow(env, token_address, amount)
+}
+
+/// Reject a claim by `user` made within the admin-set claim interval of their
+/// previous one, then record this claim's time.
+fn ensure_claim_interval(env: &Env, user: &Address) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    let interval: u64 = storage.get(&DataKey::ClaimInterval).unwrap_or(0);
+    let now = env.ledger().timestamp();
+    let key = DataKey::LastClaimAt(user.clone());
+    if let Some(last) = storage.get::<_, u64>(&key) {
+        if now < last.saturating_add(interval) {
+            return Err(ContractError::CooldownNotMet);
+        }
+    }
+    set_persistent(env, &key, &now);
+    Ok(())
+}
+
+/// Penalty on cancelling a bet of `amount` `time_to_start` seconds before its
+/// match starts: that of the tightest configured tier the cancellation falls in.
+fn cancel_penalty(env: &Env, amount: i128, time_to_start: u64) -> Result<i128, ContractError> {
+    let tiers: Vec<(u64, u32)> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CancelTiers)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut tightest: Option<(u64, u32)> = None;
+    for (within, bps) in tiers.iter() {
+        if time_to_start > within {
+            continue;
+        }
+        match tightest {
+            Some((tightest_within, _)) if tightest_within <= within => {}
+            _ => tightest = Some((within, bps)),
+        }
+    }
+    let Some((_, bps)) = tightest else {
+        return Ok(0);
+    };
+    amount
+        .checked_mul(bps as i128)
+        .map(|scaled| scaled / FEE_SCALE)
+        .ok_or(ContractError::ArithmeticOverflow)
+}
+
+/// Cancel an active bet stored at `bet_key` and return its stake to the
+/// bettor, less `penalty`, which stays in the contract for the house.
+fn refund_bet(
+    env: &Env,
+    bet_key: &DataKey,
+    mut bet: Bet,
+    reason: &str,
+    penalty: i128,
+) -> Result<(), ContractError> {
+    let storage = env.storage().persistent();
+    bet.status = BetStatus::Cancelled;
+    set_persistent(env, bet_key, &bet);
+
+    // Settled stakes stay in the volume; withdrawn ones do not
+    let volume_key = DataKey::MatchVolume(bet.match_id.clone());
+    let volume: i128 = storage.get(&volume_key).unwrap_or(0);
+    set_persistent(env, &volume_key, &(volume - bet.amount).max(0));
+    release_exposure(
+        env,
+        &bet.token_address,
+        potential_payout(bet.amount, bet.odds)?,
+    );
+    release_stake(env, &bet.token_address, bet.amount);
+    let refund = bet.amount - penalty;
+    debit_escrow(env, &bet.token_address, refund)?;
+
+    token::Client::new(env, &bet.token_address).transfer(
+        &env.current_contract_address(),
+        &bet.bettor,
+        &refund,
+    );
+
+    let event = BetCancelledEvent {
+        bettor: bet.bettor,
+        match_id: bet.match_id.clone(),
+        amount: refund,
+        reason: Symbol::new(env, reason),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events()
+        .publish((BET_CANCELLED_EVENT, bet.match_id), event);
+    Ok(())
+}
+
+fn spin_replay_hash(env: &Env, spin_id: &BytesN<32>, executor: &Address, nonce: u64) -> BytesN<32> {
+    let mut preimage = Bytes::from(spin_id.clone());
+    preimage.append(&executor.clone().to_xdr(env));
+    preimage.extend_from_array(&nonce.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Drop a resolved bet's potential payout from the global and per-token exposure.
+fn release_exposure(env: &Env, token_address: &Address, payout: i128) {
+    let storage = env.storage().persistent();
+    let exposure: i128 = storage.get(&DataKey::GlobalExposure).unwrap_or(0);
+    set_persistent(env, &DataKey::GlobalExposure, &(exposure - payout).max(0));
+
+    let token_key = DataKey::TokenExposure(token_address.clone());
+    let token_exposure: i128 = storage.get(&token_key).unwrap_or(0);
+    set_persistent(env, &token_key, &(token_exposure - payout).max(0));
+}
+
+/// Drop a resolved bet's stake from the open stakes held in its token.
+fn release_stake(env: &Env, token_address: &Address, amount: i128) {
+    let storage = env.storage().persistent();
+    let key = DataKey::OpenStakes(token_address.clone());
+    let open_stakes: i128 = storage.get(&key).unwrap_or(0);
+    set_persistent(env, &key, &(open_stakes - amount).max(0));
+}
+
+fn record_spin_execution(env: &Env, execution: &SpinExecution) {
+    let storage = env.storage().persistent();
+    let count: u32 = storage.get(&DataKey::SpinCount).unwrap_or(0);
+
+    set_persistent(
+        env,
+        &DataKey::SpinExecution(execution.spin_id.clone()),
+        execution,
+    );
+    set_persistent(env, &DataKey::SpinAt(count), &execution.spin_id);
+    set_persistent(env, &DataKey::SpinCount, &(count + 1));
+
+    let executor_key = DataKey::ExecutorSpins(execution.executor.clone());
+    let mut spins: Vec<BytesN<32>> = storage.get(&executor_key).unwrap_or_else(|| Vec::new(env));
+    spins.push_back(execution.spin_id.clone());
+    set_persistent(env, &executor_key, &spins);
+}
+
+#[contract]
+pub struct BettingContract;
+
+#[contractimpl]
+impl BettingContract {
+    /// Initialize the contract with the backend signer address and the
+    /// ed25519 public key it signs spins with. Can only be called once.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        backend_signer: Address,
+        backend_public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let storage = env.storage().persistent();
+        // Deployments from before the flag existed are recognised by their signer
+        if storage.has(&DataKey::Initialized) || storage.has(&DataKey::BackendSigner) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        set_persistent(&env, &DataKey::Initialized, &true);
+        set_persistent(&env, &DataKey::Admin, &admin);
+        set_persistent(&env, &DataKey::BackendSigner, &backend_signer);
+        set_persistent(&env, &DataKey::BackendPublicKey, &backend_public_key);
+        set_persistent(&env, &DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Initialize a development deployment where spins don't need the backend
+    /// signer's authorization. The flag can only be set here, on a fresh
+    /// contract, and has no setter, so it can never be enabled afterwards.
+    pub fn initialize_test_mode(
+        env: Env,
+        admin: Address,
+        backend_signer: Address,
+        backend_public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        Self::initialize(env.clone(), admin, backend_signer, backend_public_key)?;
+        set_persistent(&env, &DataKey::TestMode, &true);
+        Ok(())
+    }
+
+    /// Hand the backend signer role to `new_signer`, e.g. after a key
+    /// compromise, along with the ed25519 key spins must now be signed with.
+    /// Signatures from the previous key stop verifying at once. Only the
+    /// current signer can rotate it.
+    pub fn set_backend_signer(
+        env: Env,
+        current_signer: Address,
+        new_signer: Address,
+        new_public_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_backend_signer(&env, &current_signer)?;
+        set_persistent(&env, &DataKey::BackendSigner, &new_signer);
+        set_persistent(&env, &DataKey::BackendPublicKey, &new_public_key);
+        Ok(())
+    }
+
+    /// Address that co-signs spin executions
+    pub fn get_backend_signer(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BackendSigner)
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    /// Address that administers the contract. Deployments that predate the
+    /// admin role report their backend signer.
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        let storage = env.storage().persistent();
+        storage
+            .get(&DataKey::Admin)
+            .or_else(|| storage.get(&DataKey::BackendSigner))
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    /// Extend the TTL of the contract instance and its code, the roles and
+    /// settings, and the escrow, winnings and exposure totals of each of
+    /// `tokens`, so none of them is archived between calls. Entries that were
+    /// never written are skipped.
+    pub fn bump_ttl(env: Env, admin: Address, tokens: Vec<Address>) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        extend_instance(&env);
+
+        let extend = |key: &DataKey| {
+            if env.storage().persistent().has(key) {
+                extend_persistent(&env, key);
+            }
+        };
+        for key in [
+            DataKey::Admin,
+            DataKey::BackendSigner,
+            DataKey::BackendPublicKey,
+            DataKey::Initialized,
+            DataKey::SchemaVersion,
+            DataKey::TestMode,
+            DataKey::Paused,
+            DataKey::GlobalPauser,
+            DataKey::FeeBps,
+            DataKey::Treasury,
+            DataKey::BetLimits,
+            DataKey::MaxOdds,
+            DataKey::DailyLimit,
+            DataKey::GlobalExposureCap,
+            DataKey::PreventDoubleBetting,
+            DataKey::HouseCounterparty,
+            DataKey::GlobalExposure,
+            DataKey::CancelTiers,
+            DataKey::ClaimInterval,
+            DataKey::SpinCount,
+        ] {
+            extend(&key);
+        }
+        for token in tokens.iter() {
+            for key in [
+                DataKey::EscrowBalance(token.clone()),
+                DataKey::TotalClaimable(token.clone()),
+                DataKey::TokenExposure(token.clone()),
+                DataKey::OpenStakes(token),
+            ] {
+                extend(&key);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read every contract-wide setting at once
+    pub fn get_config(env: Env) -> Result<Config, ContractError> {
+        let admin = Self::get_admin(env.clone())?;
+        let limits = Self::get_bet_limits(env.clone());
+        Ok(Config {
+            admin,
+            treasury: fee_treasury(&env).ok_or(ContractError::NotInitialized)?,
+            fee_bps: Self::get_fee_bps(env.clone()),
+            min_amount: limits.map(|(min_amount, _)| min_amount),
+            max_amount: limits.map(|(_, max_amount)| max_amount),
+            max_odds: Self::get_max_odds(env.clone()),
+            daily_limit: Self::get_daily_limit(env.clone()),
+            global_exposure_cap: Self::get_global_exposure_cap(env.clone()),
+            prevent_double_betting: Self::is_double_betting_prevented(env.clone()),
+            house_counterparty: Self::is_house_counterparty(env.clone()),
+            paused: Self::is_paused(env.clone()),
+            global_pauser: Self::global_pauser(env.clone()),
+            test_mode: Self::is_test_mode(env),
+        })
+    }
+
+    /// Check if this deployment was initialized in test mode
+    pub fn is_test_mode(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TestMode)
+            .unwrap_or(false)
+    }
+
+    /// Storage schema version this contract's data is laid out in.
+    /// Deployments that predate versioning report `1`.
+    pub fn get_version(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(1)
+    }
+
+    /// Upgrade records written by an older layout to `SCHEMA_VERSION`.
+    ///
+    /// Storage can't be enumerated, so pre-v3 bets are moved only for the
+    /// `(match_id, bettor)` pairs listed in `legacy_bets`. Those deployments
+    /// didn't record the stake's token, so every listed bet is taken to be
+    /// in `legacy_token`. Pairs without a legacy record are skipped.
+    ///
+    /// Safe to call repeatedly: once the stored version is current this is a no-op.
+    pub fn migrate(
+        env: Env,
+        admin: Address,
+        legacy_token: Address,
+        legacy_bets: Vec<(BytesN<32>, Address)>,
+    ) -> Result<u32, ContractError> {
+        require_admin(&env, &admin)?;
+
+        let storage = env.storage().persistent();
+        let version = Self::get_version(env.clone());
+        if version >= SCHEMA_VERSION {
+            return Ok(version);
+        }
+
+        // v1 -> v2: split the single executions map into per-spin entries
+        let legacy: Option<Map<BytesN<32>, SpinExecution>> = storage.get(&DataKey::SpinExecutions);
+        if let Some(executions) = legacy {
+            for (_, execution) in executions.iter() {
+                record_spin_execution(&env, &execution);
+            }
+            storage.remove(&DataKey::SpinExecutions);
+        }
+
+        // v2 -> v3: re-key single bets per bettor into indexed slots
+        for (match_id, bettor) in legacy_bets.iter() {
+            migrate_legacy_bet(&env, &match_id, &bettor, &legacy_token)?;
+        }
+
+        set_persistent(&env, &DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Release of the deployed contract code.
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract code in place, keeping its storage.
+    /// Run `migrate` afterwards if the new code changes the storage layout.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    /// Register a match so bets can be placed on it until `start_time`
+    pub fn create_match(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if end_time < start_time {
+            return Err(ContractError::InvalidMatchWindow);
+        }
+
+        let storage = env.storage().persistent();
+        let key = DataKey::Match(match_id.clone());
+        if storage.has(&key) {
+            return Err(ContractError::MatchAlreadyExists);
+        }
+        set_persistent(
+            &env,
+            &key,
+            &Match {
+                match_id,
+                start_time,
+                end_time,
+            },
+        );
+        Ok(())
+    }
+
+    /// Delete an ended match's registry entry to reclaim storage, e.g. a
+    /// fixture that never drew bets or results. Refuses while any bet on it
+    /// is still active; settle, cancel or expire those first.
+    pub fn prune_match(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        ensure_match_ended(&env, &match_id)?;
+
+        let storage = env.storage().persistent();
+        let bettors: Vec<Address> = storage
+            .get(&DataKey::MatchBettors(match_id.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        for bettor in bettors.iter() {
+            for bet in load_bets(&env, &match_id, &bettor).iter() {
+                if ensure_active_bet(&bet).is_ok() {
+                    return Err(ContractError::InvalidStatus);
+                }
+            }
+        }
+
+        storage.remove(&DataKey::Match(match_id));
+        Ok(())
+    }
+
+    /// Get a registered match
+    pub fn get_match(env: Env, match_id: BytesN<32>) -> Result<Match, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Match(match_id))
+            .ok_or(ContractError::MatchNotFound)
+    }
+
+    /// Place a bet and escrow funds. `odds` is the payout multiplier in basis
+    /// points of [`ODDS_SCALE`] (10000 == 1.0x) and must be non-zero and no
+    /// higher than the ceiling set via `set_max_odds`. The match must be
+    /// registered and not yet started. `metadata` (e.g. the selection name or
+    /// market type) is stored with the bet and included in its event.
+    ///
+    /// If a house fee is set, it is taken out of `amount` and sent to the
+    /// treasury; the bet records only the remaining stake and its metadata
+    /// carries the fee under `fee`. Bet and daily limits apply to the full
+    /// `amount`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_bet(
+        env: Env,
+        bettor: Address,
+        token_address: Address,
+        amount: i128,
+        match_id: BytesN<32>,
+        bet_type: Symbol,
+        odds: u32,
+        metadata: Option<Map<Symbol, String>>,
+    ) -> Result<(), ContractError> {
+        bettor.require_auth();
+        ensure_active(&env)?;
+        ensure_not_blocked(&env, &bettor)?;
+
+        check_bet(&env, amount, &match_id, odds)?;
+        let (stake, fee) = split_fee(&env, amount)?;
+        let mut metadata = metadata.unwrap_or_else(|| Map::new(&env));
+        if fee > 0 {
+            metadata.set(Symbol::new(&env, "fee"), amount_to_string(&env, fee));
+        }
+        record_bet(
+            &env,
+            &bettor,
+            &token_address,
+            stake,
+            amount,
+            match_id,
+            bet_type,
+            odds,
+            metadata,
+            stake,
+        )?;
+        credit_escrow(&env, &token_address, stake)?;
+
+        // Lock funds (transfer from bettor to contract)
+        token::Client::new(&env, &token_address).transfer(
+            &bettor,
+            env.current_contract_address(),
+            &stake,
+        );
+        collect_fee(&env, &bettor, &token_address, fee)?;
+
+        Ok(())
+    }
+
+    /// Place several bets in one call, e.g. the legs of an accumulator, each
+    /// entry being `(match_id, bet_type, amount, odds)`. Every entry is checked
+    /// before anything is recorded and the combined stake is escrowed with a
+    /// single transfer; if any entry is rejected, no bet is placed. Each entry
+    /// pays the house fee as in `place_bet`.
+    pub fn place_bets(
+        env: Env,
+        bettor: Address,
+        token_address: Address,
+        bets: Vec<(BytesN<32>, Symbol, i128, u32)>,
+    ) -> Result<(), ContractError> {
+        bettor.require_auth();
+        ensure_active(&env)?;
+        ensure_not_blocked(&env, &bettor)?;
+
+        if bets.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        for (match_id, _, amount, odds) in bets.iter() {
+            check_bet(&env, amount, &match_id, odds)?;
+        }
+
+        // Limits that depend on earlier legs (exposure, daily cap, double
+        // betting) are enforced as each leg is recorded; a failure there
+        // reverts the legs already written along with the rest of the call.
+        let mut total: i128 = 0;
+        let mut fees: i128 = 0;
+        for (match_id, bet_type, amount, odds) in bets.iter() {
+            let (stake, fee) = split_fee(&env, amount)?;
+            total = total
+                .checked_add(stake)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            fees = fees
+                .checked_add(fee)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            let mut metadata = Map::new(&env);
+            if fee > 0 {
+                metadata.set(Symbol::new(&env, "fee"), amount_to_string(&env, fee));
+            }
+            record_bet(
+                &env,
+                &bettor,
+                &token_address,
+                stake,
+                amount,
+                match_id,
+                bet_type,
+                odds,
+                metadata,
+                total,
+            )?;
+        }
+        credit_escrow(&env, &token_address, total)?;
+
+        token::Client::new(&env, &token_address).transfer(
+            &bettor,
+            env.current_contract_address(),
+            &total,
+        );
+        collect_fee(&env, &bettor, &token_address, fees)?;
+
+        Ok(())
+    }
+
+    /// Settle the bettor's active bets on `match_id` and credit their winnings
+    /// from escrow, to be collected with `claim`. A win pays
+    /// `amount * odds / ODDS_SCALE`, a draw (push) refunds exactly the escrowed
+    /// stake, and a loss keeps the stake in the contract. The house fee taken
+    /// at placement is never returned. The match must have reached its
+    /// `end_time`. Returns the total amount credited to the bettor.
+    pub fn settle_bet(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+        bettor: Address,
+        outcome: BetOutcome,
+    ) -> Result<i128, ContractError> {
+        require_admin(&env, &admin)?;
+        ensure_match_ended(&env, &match_id)?;
+
+        if bet_count(&env, &match_id, &bettor) == 0 {
+            return Err(ContractError::BetNotFound);
+        }
+
+        let settled = settle_bettor(&env, &match_id, &bettor, &outcome)?;
+        // Nothing was left to settle: report why the most recent bet is closed
+        if settled.count == 0 {
+            let latest = Self::get_bet(env, match_id, bettor)?;
+            ensure_active_bet(&latest)?;
+        }
+
+        Ok(settled.payout)
+    }
+
+    /// Correct `bettor`'s settled bets on `match_id` to `outcome`, e.g. after
+    /// a dispute found the wrong result was used. The difference between the
+    /// old and new payouts is added to or taken from their claimable
+    /// winnings, so this is refused once a payout has been claimed. Returns
+    /// the corrected payout.
+    pub fn resettle_bet(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+        bettor: Address,
+        outcome: BetOutcome,
+    ) -> Result<i128, ContractError> {
+        require_admin(&env, &admin)?;
+
+        let count = bet_count(&env, &match_id, &bettor);
+        if count == 0 {
+            return Err(ContractError::BetNotFound);
+        }
+
+        let storage = env.storage().persistent();
+        let mut corrected_any = false;
+        let mut previous: i128 = 0;
+        let mut corrected: i128 = 0;
+        for index in 0..count {
+            let settlement_key = DataKey::BetSettlement(match_id.clone(), bettor.clone(), index);
+            let Some((_, paid, claims)) =
+                storage.get::<_, (BetOutcome, i128, u32)>(&settlement_key)
+            else {
+                continue;
+            };
+            let bet_key = DataKey::Bet(match_id.clone(), bettor.clone(), index);
+            let bet: Bet = storage.get(&bet_key).ok_or(ContractError::BetNotFound)?;
+
+            let claims_now: u32 = storage
+                .get(&DataKey::ClaimCount(
+                    bettor.clone(),
+                    bet.token_address.clone(),
+                ))
+                .unwrap_or(0);
+            if paid > 0 && claims_now != claims {
+                return Err(ContractError::InvalidStatus);
+            }
+
+            let payout = match outcome {
+                BetOutcome::Win => potential_payout(bet.amount, bet.odds)?,
+                BetOutcome::Lose => 0,
+                BetOutcome::Draw => bet.amount,
+            };
+            let delta = payout - paid;
+            let claimable_key = DataKey::Claimable(bettor.clone(), bet.token_address.clone());
+            let claimable: i128 = storage.get(&claimable_key).unwrap_or(0);
+            let total_key = DataKey::TotalClaimable(bet.token_address.clone());
+            let total: i128 = storage.get(&total_key).unwrap_or(0);
+            set_persistent(
+                &env,
+                &claimable_key,
+                &claimable
+                    .checked_add(delta)
+                    .ok_or(ContractError::ArithmeticOverflow)?,
+            );
+            set_persistent(
+                &env,
+                &total_key,
+                &total
+                    .checked_add(delta)
+                    .ok_or(ContractError::ArithmeticOverflow)?,
+            );
+            set_persistent(&env, &settlement_key, &(outcome, payout, claims_now));
+
+            corrected_any = true;
+            previous = previous
+                .checked_add(paid)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            corrected = corrected
+                .checked_add(payout)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+        if !corrected_any {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let event = BetResettledEvent {
+            bettor,
+            match_id: match_id.clone(),
+            previous_amount: previous,
+            amount: corrected,
+            settlement_type: Symbol::new(&env, settlement_type(&outcome)),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((BET_RESETTLED_EVENT, match_id), event);
+        Ok(corrected)
+    }
+
+    /// Settle every active bet on `match_id` with `winning_outcome`, skipping
+    /// bets that are already closed. Returns the number of bets settled.
+    pub fn settle_match(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+        winning_outcome: BetOutcome,
+    ) -> Result<u32, ContractError> {
+        require_admin(&env, &admin)?;
+        ensure_match_ended(&env, &match_id)?;
+
+        let mut total = Settled::default();
+        for bettor in Self::get_match_bettors(env.clone(), match_id.clone()).iter() {
+            let settled = settle_bettor(&env, &match_id, &bettor, &winning_outcome)?;
+            total.count += settled.count;
+            total.staked = total
+                .staked
+                .checked_add(settled.staked)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            total.payout = total
+                .payout
+                .checked_add(settled.payout)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+
+        let event = MatchSettledEvent {
+            match_id: match_id.clone(),
+            winning_outcome: Symbol::new(&env, settlement_type(&winning_outcome)),
+            total_bets: total.count,
+            total_staked: total.staked,
+            total_payout: total.payout,
+            house_profit: total.staked - total.payout,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((MATCH_SETTLED_EVENT, match_id), event);
+
+        Ok(total.count)
+    }
+
+    /// Get the bettor's most recent bet on `match_id` and its current status
+    pub fn get_bet(env: Env, match_id: BytesN<32>, bettor: Address) -> Result<Bet, ContractError> {
+        let count = bet_count(&env, &match_id, &bettor);
+        if count == 0 {
+            return Err(ContractError::BetNotFound);
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bet(match_id, bettor, count - 1))
+            .ok_or(ContractError::BetNotFound)
+    }
+
+    /// List every bet `bettor` has placed on `match_id`, oldest first
+    pub fn get_bettor_bets(env: Env, match_id: BytesN<32>, bettor: Address) -> Vec<Bet> {
+        load_bets(&env, &match_id, &bettor)
+    }
+
+    /// List every match `bettor` has placed a bet on, in the order first bet on
+    pub fn get_bettor_matches(env: Env, bettor: Address) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BettorMatches(bettor))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Number of distinct addresses that have ever placed a bet
+    pub fn total_bettors(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalBettors)
+            .unwrap_or(0)
+    }
+
+    /// List every address that has placed a bet on `match_id`
+    pub fn get_match_bettors(env: Env, match_id: BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchBettors(match_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// List every bet placed on `match_id`, grouped by bettor in the order
+    /// bettors first joined
+    pub fn get_match_bets(env: Env, match_id: BytesN<32>) -> Vec<Bet> {
+        let mut bets = Vec::new(&env);
+        for bettor in Self::get_match_bettors(env.clone(), match_id.clone()).iter() {
+            bets.append(&load_bets(&env, &match_id, &bettor));
+        }
+        bets
+    }
+
+    /// Total stake wagered on `match_id`, excluding cancelled bets
+    pub fn get_match_volume(env: Env, match_id: BytesN<32>) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchVolume(match_id))
+            .unwrap_or(0)
+    }
+
+    /// Withdraw the bettor's unsettled bets on `match_id` and refund the
+    /// escrowed stakes. Only possible before the match starts. Cancelling
+    /// close to the start may cost a penalty, see `set_cancel_tiers`.
+    pub fn cancel_bet(
+        env: Env,
+        bettor: Address,
+        match_id: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        bettor.require_auth();
+
+        let storage = env.storage().persistent();
+        let fixture: Match = storage
+            .get(&DataKey::Match(match_id.clone()))
+            .ok_or(ContractError::MatchNotFound)?;
+        if env.ledger().timestamp() >= fixture.start_time {
+            return Err(ContractError::MatchClosed);
+        }
+
+        let count = bet_count(&env, &match_id, &bettor);
+        if count == 0 {
+            return Err(ContractError::BetNotFound);
+        }
+
+        let time_to_start = fixture.start_time - env.ledger().timestamp();
+        let mut cancelled_any = false;
+        let mut inactive = Ok(());
+        for index in 0..count {
+            let bet_key = DataKey::Bet(match_id.clone(), bettor.clone(), index);
+            let bet: Bet = storage.get(&bet_key).ok_or(ContractError::BetNotFound)?;
+            if let Err(err) = ensure_active_bet(&bet) {
+                inactive = Err(err);
+                continue;
+            }
+
+            let penalty = cancel_penalty(&env, bet.amount, time_to_start)?;
+            refund_bet(&env, &bet_key, bet, "bettor_cancelled", penalty)?;
+            cancelled_any = true;
+        }
+
+        // Nothing was left to cancel: report why the most recent bet is closed
+        if !cancelled_any {
+            inactive?;
+        }
+
+        Ok(())
+    }
+
+    /// Refund every active bet on a match that was called off, e.g. cancelled
+    /// off-chain with bettors who never withdrew. Settled and already-cancelled
+    /// bets are left alone. Returns the number of bets refunded.
+    pub fn expire_match(
+        env: Env,
+        admin: Address,
+        match_id: BytesN<32>,
+    ) -> Result<u32, ContractError> {
+        require_admin(&env, &admin)?;
+
+        let storage = env.storage().persistent();
+        let bettors: Vec<Address> = storage
+            .get(&DataKey::MatchBettors(match_id.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut refunded = 0u32;
+        for bettor in bettors.iter() {
+            for index in 0..bet_count(&env, &match_id, &bettor) {
+                let bet_key = DataKey::Bet(match_id.clone(), bettor.clone(), index);
+                let bet: Bet = storage.get(&bet_key).ok_or(ContractError::BetNotFound)?;
+                if ensure_active_bet(&bet).is_err() {
+                    continue;
+                }
+                refund_bet(&env, &bet_key, bet, "EXPIRED", 0)?;
+                refunded += 1;
+            }
+        }
+        Ok(refunded)
+    }
+
+    /// Configure double betting prevention
+    pub fn set_prevent_double_betting(
+        env: Env,
+        admin: Address,
+        prevent: bool,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+
+        set_persistent(&env, &DataKey::PreventDoubleBetting, &prevent);
+        Ok(())
+    }
+
+    /// Cap the total potential payout liability across all open bets
+    pub fn set_global_exposure_cap(
+        env: Env,
+        admin: Address,
+        cap: i128,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if cap < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        set_persistent(&env, &DataKey::GlobalExposureCap, &cap);
+        Ok(())
+    }
+
+    /// Restrict each bet's stake to `min_amount..=max_amount`
+    pub fn set_bet_limits(
+        env: Env,
+        admin: Address,
+        min_amount: i128,
+        max_amount: i128,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if min_amount <= 0 || min_amount > max_amount {
+            return Err(ContractError::InvalidAmount);
+        }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct SpinExecution {
-    pub spin_id: BytesN<32>,
-    pub executor: Address,
-    pub timestamp: u64,
-}
+        set_persistent(&env, &DataKey::BetLimits, &(min_amount, max_amount));
+        Ok(())
+    }
 
-#[contracttype]
-#[derive(Clone)]
-pub struct Bet {
-    pub bettor: Address,
-    pub amount: i128,
-    pub match_id: BytesN<32>,
-    pub bet_type: Symbol,
-    pub odds: u32,
-    pub timestamp: u64,
-}
+    /// Get the configured `(min_amount, max_amount)` stake limits, if any
+    pub fn get_bet_limits(env: Env) -> Option<(i128, i128)> {
+        env.storage().persistent().get(&DataKey::BetLimits)
+    }
 
-#[contracttype]
-pub enum DataKey {
-    BackendSigner,
-    UsedSpinHashes,
-    SpinExecutions,
-    Bet(BytesN<32>, Address),
-    PreventDoubleBetting,
-}
+    /// Cap the total a single bettor may stake within any [`DAILY_WINDOW_SECONDS`] window
+    pub fn set_daily_limit(env: Env, admin: Address, limit: i128) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if limit <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
 
-#[contract]
-pub struct BettingContract;
+        set_persistent(&env, &DataKey::DailyLimit, &limit);
+        Ok(())
+    }
 
-#[contractimpl]
-impl BettingContract {
-    /// Initialize the contract with the backend signer address
-    pub fn initialize(env: Env, backend_signer: Address) {
-        let storage = env.storage().persistent();
-        storage.set(&DataKey::BackendSigner, &backend_signer);
+    /// Get the configured per-bettor daily stake limit, if any
+    pub fn get_daily_limit(env: Env) -> Option<i128> {
+        env.storage().persistent().get(&DataKey::DailyLimit)
     }
 
-    /// Place a bet and escrow funds
-    pub fn place_bet(
+    /// Set the house fee taken from each wager, in [`FEE_SCALE`] basis points.
+    /// Must be below 10000; zero disables the fee.
+    pub fn set_fee_bps(env: Env, admin: Address, fee_bps: u32) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if fee_bps as i128 >= FEE_SCALE {
+            return Err(ContractError::InvalidFee);
+        }
+        set_persistent(&env, &DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Charge a penalty on `cancel_bet` by how close to the match start it
+    /// comes. Each tier `(within, penalty_bps)` applies to cancellations at
+    /// most `within` seconds before the start; the tightest matching tier
+    /// wins and cancelling outside every tier is free. Penalties are in
+    /// [`FEE_SCALE`] basis points, at most 10000, and stay with the house.
+    pub fn set_cancel_tiers(
+        env: Env,
+        admin: Address,
+        tiers: Vec<(u64, u32)>,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        for (_, penalty_bps) in tiers.iter() {
+            if penalty_bps as i128 > FEE_SCALE {
+                return Err(ContractError::InvalidFee);
+            }
+        }
+        set_persistent(&env, &DataKey::CancelTiers, &tiers);
+        Ok(())
+    }
+
+    /// Get the cancellation penalty tiers, see `set_cancel_tiers`
+    pub fn get_cancel_tiers(env: Env) -> Vec<(u64, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CancelTiers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Require at least `interval` seconds between a user's `claim` and
+    /// `claim_rewards` payouts. Zero, the default, disables the throttle.
+    pub fn set_claim_interval(
+        env: Env,
+        admin: Address,
+        interval: u64,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        set_persistent(&env, &DataKey::ClaimInterval, &interval);
+        Ok(())
+    }
+
+    /// Get the minimum seconds between a user's claims, see `set_claim_interval`
+    pub fn get_claim_interval(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimInterval)
+            .unwrap_or(0)
+    }
+
+    /// Get the house fee in [`FEE_SCALE`] basis points
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Send house fees to `treasury` instead of the admin
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        set_persistent(&env, &DataKey::Treasury, &treasury);
+        Ok(())
+    }
+
+    /// Set the highest odds (in [`ODDS_SCALE`] basis points) a bet may be placed at
+    pub fn set_max_odds(env: Env, admin: Address, max_odds: u32) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if max_odds == 0 {
+            return Err(ContractError::InvalidOdds);
+        }
+
+        set_persistent(&env, &DataKey::MaxOdds, &max_odds);
+        Ok(())
+    }
+
+    /// Get the configured odds ceiling, if any
+    pub fn get_max_odds(env: Env) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::MaxOdds)
+    }
+
+    /// Get the configured global exposure cap, if any
+    pub fn get_global_exposure_cap(env: Env) -> Option<i128> {
+        env.storage().persistent().get(&DataKey::GlobalExposureCap)
+    }
+
+    /// Get the current potential payout liability across all open bets
+    pub fn get_global_exposure(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GlobalExposure)
+            .unwrap_or(0)
+    }
+
+    /// Gross winnings a stake of `amount` at `odds` would pay out, computed
+    /// exactly as `settle_bet` does, so frontends can show it before betting.
+    pub fn preview_payout(_env: Env, amount: i128, odds: u32) -> Result<i128, ContractError> {
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if odds == 0 {
+            return Err(ContractError::InvalidOdds);
+        }
+        potential_payout(amount, odds)
+    }
+
+    /// Amount the bettor would receive if their active bets on `match_id` won
+    /// now. No fees or house edge are deducted from winnings yet, so this is
+    /// the gross payout at each bet's locked-in odds.
+    pub fn effective_payout(
         env: Env,
+        match_id: BytesN<32>,
         bettor: Address,
+    ) -> Result<i128, ContractError> {
+        let bets = load_bets(&env, &match_id, &bettor);
+        if bets.is_empty() {
+            return Err(ContractError::BetNotFound);
+        }
+
+        let mut total: i128 = 0;
+        for bet in bets.iter().filter(|bet| bet.status == BetStatus::Active) {
+            total = total
+                .checked_add(potential_payout(bet.amount, bet.odds)?)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+        Ok(total)
+    }
+
+    /// Add house liquidity in `token_address`, e.g. to cover winning payouts
+    /// beyond the stakes escrowed. Tokens sent to the contract any other way
+    /// are not counted and can't be paid out.
+    pub fn deposit(
+        env: Env,
+        from: Address,
         token_address: Address,
         amount: i128,
-        match_id: BytesN<32>,
-        bet_type: Symbol,
-        odds: u32,
     ) -> Result<(), ContractError> {
-        bettor.require_auth();
+        from.require_auth();
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        credit_escrow(&env, &token_address, amount)?;
+        token::Client::new(&env, &token_address).transfer(
+            &from,
+            env.current_contract_address(),
+            &amount,
+        );
+        Ok(())
+    }
 
+    /// Funds the contract holds in `token_address` by its own accounting:
+    /// escrowed stakes and deposits, less everything paid out
+    pub fn get_escrow_balance(env: Env, token_address: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowBalance(token_address))
+            .unwrap_or(0)
+    }
+
+    /// Sweep house funds (e.g. stakes kept from lost bets) to `to`. Unclaimed
+    /// winnings and the stakes of active bets can't be withdrawn.
+    pub fn withdraw(
+        env: Env,
+        admin: Address,
+        token_address: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
         if amount <= 0 {
             return Err(ContractError::InvalidAmount);
         }
 
-        let storage = env.storage().persistent();
+        debit_free_escrow(&env, &token_address, amount)?;
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+        Ok(())
+    }
 
-        // Check if double betting is prevented
-        let prevent_double: bool = storage.get(&DataKey::PreventDoubleBetting).unwrap_or(false);
-        if prevent_double {
-            if storage.has(&DataKey::Bet(match_id.clone(), bettor.clone())) {
-                return Err(ContractError::BetAlreadyPlaced);
-            }
+    /// Settled winnings `winner` can claim in `token_address`
+    pub fn get_claimable(env: Env, winner: Address, token_address: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claimable(winner, token_address))
+            .unwrap_or(0)
+    }
+
+    /// Transfer all of `winner`'s settled winnings in `token_address` to them.
+    /// Returns the amount paid. Blocked addresses keep their winnings credited
+    /// but can't collect them until unblocked.
+    pub fn claim(env: Env, winner: Address, token_address: Address) -> Result<i128, ContractError> {
+        winner.require_auth();
+        ensure_active(&env)?;
+        ensure_not_blocked(&env, &winner)?;
+
+        let storage = env.storage().persistent();
+        let claimable_key = DataKey::Claimable(winner.clone(), token_address.clone());
+        let amount: i128 = storage.get(&claimable_key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(ContractError::InsufficientBalance);
         }
+        ensure_claim_interval(&env, &winner)?;
 
-        // Lock funds (transfer from bettor to contract)
-        let token_client = token::Client::new(&env, &token_address);
-        token_client.transfer(&bettor, &env.current_contract_address(), &amount);
+        let total_key = DataKey::TotalClaimable(token_address.clone());
+        let total: i128 = storage.get(&total_key).unwrap_or(0);
+        storage.remove(&claimable_key);
+        set_persistent(&env, &total_key, &(total - amount).max(0));
+        debit_escrow(&env, &token_address, amount)?;
 
-        // Store bet
-        let timestamp = env.ledger().timestamp();
-        let bet = Bet {
-            bettor: bettor.clone(),
-            amount,
-            match_id: match_id.clone(),
-            bet_type: bet_type.clone(),
-            odds,
-            timestamp,
-        };
+        let claims_key = DataKey::ClaimCount(winner.clone(), token_address.clone());
+        let claims: u32 = storage.get(&claims_key).unwrap_or(0);
+        set_persistent(&env, &claims_key, &claims.saturating_add(1));
 
-        storage.set(&DataKey::Bet(match_id.clone(), bettor.clone()), &bet);
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &winner,
+            &amount,
+        );
+        Ok(amount)
+    }
 
-        // Emit bet placed event
-        let event = BetPlacedEvent {
-            bettor: bettor.clone(),
-            bet_id: Symbol::new(&env, "bet"), // Using a generic symbol or match_id as symbol
-            amount,
-        };
-        // Note: The common::BetPlacedEvent uses Symbol for bet_id.
-        // We might want to emit a more detailed event or use Match ID.
-        // For now, let's satisfy the criteria with what's available.
-        env.events()
-            .publish((Symbol::new(&env, "bet_placed"), match_id.clone()), event);
+    /// Get the potential payout liability of open bets denominated in `token_address`
+    pub fn get_token_exposure(env: Env, token_address: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenExposure(token_address))
+            .unwrap_or(0)
+    }
 
-        Ok(())
+    /// Get the stakes of open bets escrowed in `token_address`
+    pub fn get_open_stakes(env: Env, token_address: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenStakes(token_address))
+            .unwrap_or(0)
     }
 
-    /// Configure double betting prevention
-    pub fn set_prevent_double_betting(
+    /// Require the contract's own liquidity to cover potential payouts before
+    /// accepting a bet (the house acting as counterparty to fixed-odds bets)
+    pub fn set_house_counterparty(
         env: Env,
         admin: Address,
-        prevent: bool,
+        enabled: bool,
     ) -> Result<(), ContractError> {
-        // Only backend signer (acting as admin) can change settings
-        // In a real scenario, you'd have a separate admin role
-        let storage = env.storage().persistent();
-        let backend_signer: Address = storage
-            .get(&DataKey::BackendSigner)
-            .ok_or(ContractError::Unauthorized)?;
-
-        admin.require_auth();
-        if admin != backend_signer {
-            return Err(ContractError::Unauthorized);
-        }
-
-        storage.set(&DataKey::PreventDoubleBetting, &prevent);
+        require_admin(&env, &admin)?;
+        set_persistent(&env, &DataKey::HouseCounterparty, &enabled);
         Ok(())
     }
 
+    /// Check if place_bet enforces house liquidity
+    pub fn is_house_counterparty(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HouseCounterparty)
+            .unwrap_or(false)
+    }
+
     /// Check if double betting is prevented
     pub fn is_double_betting_prevented(env: Env) -> bool {
         env.storage()
@@ -132,25 +1969,78 @@ impl BettingContract {
             .unwrap_or(false)
     }
 
+    /// Pause or resume betting and spins on this contract
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        set_persistent(&env, &DataKey::Paused, &paused);
+        Ok(())
+    }
+
+    /// Check the local pause flag. Other contracts consult this when they use
+    /// this one as their global pauser, so it deliberately ignores our own pauser.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Halt this contract whenever `pauser` reports itself paused
+    pub fn set_global_pauser(
+        env: Env,
+        admin: Address,
+        pauser: Address,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        set_persistent(&env, &DataKey::GlobalPauser, &pauser);
+        Ok(())
+    }
+
+    /// Get the contract consulted for a global pause, if any
+    pub fn global_pauser(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::GlobalPauser)
+    }
+
+    /// Block an address from placing bets or executing spins (compliance)
+    pub fn block_address(env: Env, admin: Address, addr: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        set_persistent(&env, &DataKey::Blocked(addr), &true);
+        Ok(())
+    }
+
+    /// Lift a block previously placed with `block_address`
+    pub fn unblock_address(env: Env, admin: Address, addr: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        env.storage().persistent().remove(&DataKey::Blocked(addr));
+        Ok(())
+    }
+
+    /// Check if an address is blocked
+    pub fn is_blocked(env: Env, addr: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Blocked(addr))
+    }
+
     /// Execute a spin with backend signature verification
     ///
     /// # Arguments
     /// * `spin_id` - Unique identifier for the spin (32-byte hash)
-    /// * `spin_hash` - Hash of spin parameters for replay protection
-    /// * `signature` - Signature from backend signer
+    /// * `spin_hash` - Hash of spin parameters, covered by the backend signature
+    /// * `signature` - Backend ed25519 signature over `spin_id || spin_hash`
     /// * `executor` - Address executing the spin
+    /// * `nonce` - The executor's next nonce, see `get_nonce`
     ///
     /// # Returns
-    /// `Result<(), ContractError>`
-    /// Execute a spin with backend authorization
+    /// `Result<(), ContractError>`. A signature that doesn't verify against the
+    /// backend public key traps in the host instead of returning an error.
     pub fn execute_spin(
         env: Env,
         spin_id: BytesN<32>,
         spin_hash: BytesN<32>,
         signature: BytesN<64>,
         executor: Address,
+        nonce: u64,
     ) -> Result<(), ContractError> {
-        Self::execute_spin_with_ttl(env, spin_id, spin_hash, signature, executor, None)
+        Self::execute_spin_with_ttl(env, spin_id, spin_hash, signature, executor, nonce, None)
     }
 
     pub fn execute_spin_with_ttl(
@@ -159,9 +2049,62 @@ impl BettingContract {
         spin_hash: BytesN<32>,
         signature: BytesN<64>,
         executor: Address,
+        nonce: u64,
+        ttl_seconds: Option<u64>,
+    ) -> Result<(), ContractError> {
+        Self::run_spin(
+            env,
+            spin_id,
+            spin_hash,
+            signature,
+            executor,
+            nonce,
+            ttl_seconds,
+            None,
+        )
+    }
+
+    /// Execute a spin and credit `reward` to the executor's pending rewards,
+    /// claimable later via `claim_rewards`. The reward is always authorized by
+    /// the backend signer, even in test mode.
+    pub fn execute_spin_with_reward(
+        env: Env,
+        spin_id: BytesN<32>,
+        spin_hash: BytesN<32>,
+        signature: BytesN<64>,
+        executor: Address,
+        nonce: u64,
+        reward: SpinReward,
+    ) -> Result<(), ContractError> {
+        if reward.reward_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        Self::run_spin(
+            env,
+            spin_id,
+            spin_hash,
+            signature,
+            executor,
+            nonce,
+            None,
+            Some(reward),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn run_spin(
+        env: Env,
+        spin_id: BytesN<32>,
+        spin_hash: BytesN<32>,
+        signature: BytesN<64>,
+        executor: Address,
+        nonce: u64,
         ttl_seconds: Option<u64>,
+        reward: Option<SpinReward>,
     ) -> Result<(), ContractError> {
         executor.require_auth();
+        ensure_active(&env)?;
+        ensure_not_blocked(&env, &executor)?;
 
         let storage = env.storage().persistent();
 
@@ -170,24 +2113,39 @@ impl BettingContract {
             .get(&DataKey::BackendSigner)
             .ok_or(ContractError::Unauthorized)?;
 
-        // Signature verification hook; auth is currently enforced via backend signer auth.
-        let _ = signature;
-        backend_signer.require_auth();
+        // Test mode relaxes backend auth for plain spins only, never for payouts
+        let test_mode: bool = storage.get(&DataKey::TestMode).unwrap_or(false);
+        if !test_mode || reward.is_some() {
+            backend_signer.require_auth();
+        }
+        if !test_mode {
+            // The backend signs spin_id || spin_hash; a bad signature traps in the host
+            let public_key: BytesN<32> = storage
+                .get(&DataKey::BackendPublicKey)
+                .ok_or(ContractError::InvalidSignature)?;
+            let mut message = Bytes::from(spin_id.clone());
+            message.append(&Bytes::from(spin_hash.clone()));
+            env.crypto()
+                .ed25519_verify(&public_key, &message, &signature);
+        }
 
+        // Replay protection keys on a hash the contract derives itself, so a
+        // caller can't dodge it by presenting a fresh precomputed spin_hash
+        let nonce_key = DataKey::Nonce(executor.clone());
+        if nonce != storage.get(&nonce_key).unwrap_or(0) {
+            return Err(ContractError::InvalidNonce);
+        }
         ensure_not_replayed(
             &env,
             Symbol::new(&env, "spin_exec"),
-            spin_hash.clone(),
+            spin_replay_hash(&env, &spin_id, &executor, nonce),
             ttl_seconds,
         )?;
-
-        // Store spin execution
-        let executions: Map<BytesN<32>, SpinExecution> = storage
-            .get(&DataKey::SpinExecutions)
-            .unwrap_or_else(|| Map::new(&env));
+        storage.set(&nonce_key, &(nonce + 1));
+        extend_persistent(&env, &nonce_key);
 
         // Check for duplicate execution on spin ID
-        if executions.get(spin_id.clone()).is_some() {
+        if storage.has(&DataKey::SpinExecution(spin_id.clone())) {
             return Err(ContractError::SpinAlreadyExecuted);
         }
 
@@ -199,10 +2157,8 @@ impl BettingContract {
             timestamp: current_time,
         };
 
-        // Update storage
-        let mut new_executions = executions.clone();
-        new_executions.set(spin_id.clone(), execution.clone());
-        storage.set(&DataKey::SpinExecutions, &new_executions);
+        // Store spin execution
+        record_spin_execution(&env, &execution);
         // Emit execution event
         let event = SpinExecutedEvent {
             spin_id: spin_id.clone(),
@@ -210,20 +2166,79 @@ impl BettingContract {
             timestamp: current_time,
         };
 
+        // Executor is indexed as a topic so per-player spin feeds can be filtered via getEvents.
         env.events()
-            .publish((Symbol::new(&env, "spin_executed"),), event);
+            .publish((SPIN_EXECUTED_EVENT, executor.clone()), event);
+
+        if let Some(reward) = reward {
+            // Rewards accrue here and are paid out by claim_rewards, so the
+            // contract needn't hold liquidity at spin time
+            let reward_key = DataKey::PendingReward(executor.clone(), reward.token_address.clone());
+            let pending: i128 = storage.get(&reward_key).unwrap_or(0);
+            let pending = pending
+                .checked_add(reward.reward_amount)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            set_persistent(&env, &reward_key, &pending);
+
+            let event = create_spin_reward_event(
+                &env,
+                executor.clone(),
+                reward.reward_amount,
+                reward.token_address,
+                env.current_contract_address(),
+                U256::from_be_bytes(&env, &Bytes::from(spin_id)),
+                Symbol::new(&env, "token"),
+                reward.multiplier,
+            );
+            env.events().publish((SPIN_REWARD_EVENT, executor), event);
+        }
 
         Ok(())
     }
 
-    /// Check if a spin has already been executed
-    pub fn is_spin_executed(env: Env, spin_id: BytesN<32>) -> bool {
+    /// Spin rewards credited to `executor` in `token_address` and not yet claimed
+    pub fn get_pending_reward(env: Env, executor: Address, token_address: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingReward(executor, token_address))
+            .unwrap_or(0)
+    }
+
+    /// Pay out every spin reward `executor` has accrued in `token_address`.
+    /// Returns the amount transferred.
+    pub fn claim_rewards(
+        env: Env,
+        executor: Address,
+        token_address: Address,
+    ) -> Result<i128, ContractError> {
+        executor.require_auth();
+        ensure_active(&env)?;
+        ensure_not_blocked(&env, &executor)?;
+
+        let key = DataKey::PendingReward(executor.clone(), token_address.clone());
         let storage = env.storage().persistent();
-        let executions: Map<BytesN<32>, SpinExecution> = storage
-            .get(&DataKey::SpinExecutions)
-            .unwrap_or_else(|| Map::new(&env));
+        let pending: i128 = storage.get(&key).unwrap_or(0);
+        if pending == 0 {
+            return Ok(0);
+        }
+        ensure_claim_interval(&env, &executor)?;
+
+        storage.remove(&key);
+        // Paid from house funds only, never from stakes or winnings owed to bettors
+        debit_free_escrow(&env, &token_address, pending)?;
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &executor,
+            &pending,
+        );
+        Ok(pending)
+    }
 
-        executions.get(spin_id).is_some()
+    /// Check if a spin has already been executed
+    pub fn is_spin_executed(env: Env, spin_id: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::SpinExecution(spin_id))
     }
 
     /// Get spin execution details
@@ -231,20 +2246,99 @@ impl BettingContract {
         env: Env,
         spin_id: BytesN<32>,
     ) -> Result<SpinExecution, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SpinExecution(spin_id))
+            .ok_or(ContractError::SpinNotFound)
+    }
+
+    /// List spin executions in execution order, `limit` entries from `start`.
+    ///
+    /// `limit` is clamped to `MAX_PAGE_SIZE`. Returns the page together with
+    /// the cursor to pass as the next `start`, or `None` once all executions
+    /// have been returned.
+    pub fn list_spin_executions(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<SpinExecution>, Option<u32>) {
         let storage = env.storage().persistent();
-        let executions: Map<BytesN<32>, SpinExecution> = storage
-            .get(&DataKey::SpinExecutions)
-            .unwrap_or_else(|| Map::new(&env));
+        let count: u32 = storage.get(&DataKey::SpinCount).unwrap_or(0);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            let spin_id: BytesN<32> = storage.get(&DataKey::SpinAt(index)).unwrap();
+            if let Some(execution) = storage.get(&DataKey::SpinExecution(spin_id)) {
+                page.push_back(execution);
+            }
+        }
+
+        let cursor = if end < count { Some(end) } else { None };
+        (page, cursor)
+    }
+
+    /// Every spin id `executor` has executed, oldest first
+    pub fn get_executor_spins(env: Env, executor: Address) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ExecutorSpins(executor))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Page through the spin ids `executor` has executed, oldest first,
+    /// starting at position `start`.
+    ///
+    /// `limit` is clamped to `MAX_PAGE_SIZE`. Returns the page together with
+    /// the cursor to pass as the next `start`, or `None` once all spins have
+    /// been returned.
+    pub fn list_executor_spins(
+        env: Env,
+        executor: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<BytesN<32>>, Option<u32>) {
+        let spins = Self::get_executor_spins(env, executor);
+        let count = spins.len();
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let page = if start < end {
+            spins.slice(start..end)
+        } else {
+            Vec::new(spins.env())
+        };
+
+        let cursor = if end < count { Some(end) } else { None };
+        (page, cursor)
+    }
+
+    /// Next nonce `executor` must pass to `execute_spin`
+    pub fn get_nonce(env: Env, executor: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Nonce(executor))
+            .unwrap_or(0)
+    }
 
-        executions.get(spin_id).ok_or(ContractError::SpinNotFound)
+    /// Replay-protection hash recorded for a spin, `sha256(spin_id || executor || nonce)`
+    pub fn spin_replay_hash(
+        env: Env,
+        spin_id: BytesN<32>,
+        executor: Address,
+        nonce: u64,
+    ) -> BytesN<32> {
+        spin_replay_hash(&env, &spin_id, &executor, nonce)
     }
 
-    pub fn is_spin_hash_used(env: Env, spin_hash: BytesN<32>) -> bool {
-        is_operation_executed(&env, Symbol::new(&env, "spin_exec"), spin_hash)
+    /// Whether a spin's replay record is still held. Takes the key from
+    /// `spin_replay_hash`, not the spin hash the backend signed.
+    pub fn is_spin_hash_used(env: Env, replay_hash: BytesN<32>) -> bool {
+        is_operation_executed(&env, Symbol::new(&env, "spin_exec"), replay_hash)
     }
 
-    pub fn cleanup_spin_hash(env: Env, spin_hash: BytesN<32>) -> bool {
-        cleanup_operation(&env, Symbol::new(&env, "spin_exec"), spin_hash)
+    /// Drop a spin's expired replay record. Takes the key from
+    /// `spin_replay_hash`, not the spin hash the backend signed.
+    pub fn cleanup_spin_hash(env: Env, replay_hash: BytesN<32>) -> bool {
+        cleanup_operation(&env, Symbol::new(&env, "spin_exec"), replay_hash)
     }
 }
 #[cfg(test)]
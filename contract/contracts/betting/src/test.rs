@@ -1,188 +1,3373 @@
 #![cfg(test)]
 
 use super::*;
+use common::{
+    BetCancelledEvent, BetEvent, BetOutcome, BetResettledEvent, BetStatus, MatchSettledEvent,
+    SettlementEvent, SpinRewardEvent, WinningsAvailableEvent, BET_CANCELLED_EVENT, BET_EVENT,
+    BET_RESETTLED_EVENT, MATCH_SETTLED_EVENT, SETTLEMENT_EVENT, SPIN_EXECUTED_EVENT,
+    SPIN_REWARD_EVENT, WINNINGS_AVAILABLE_EVENT,
+};
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, BytesN, Env, Symbol,
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Events, Ledger, MockAuth, MockAuthInvoke,
+    },
+    token, vec, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec, U256,
 };
 
-fn setup_test(env: &Env) -> (BettingContractClient<'_>, Address, Address) {
+/// Betting on the matches registered by `setup_test` closes at this time.
+const MATCH_START: u64 = 1_000;
+const MATCH_END: u64 = 2_000;
+
+/// Events published by `contract`, in order.
+fn contract_events(env: &Env, contract: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+    let mut events = Vec::new(env);
+    for event in env.events().all().iter() {
+        if event.0 == *contract {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+/// Initializes the contract and registers a match for every `[seed; 32]` id
+/// with seed below 16, open until `MATCH_START`.
+fn setup_test(env: &Env) -> (BettingContractClient<'_>, Address, Address) {
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let backend_signer = Address::generate(env);
+    let bettor = Address::generate(env);
+    client.initialize(&admin, &backend_signer, &backend_public_key(env));
+    for seed in 0..16u8 {
+        client.create_match(
+            &admin,
+            &BytesN::from_array(env, &[seed; 32]),
+            &MATCH_START,
+            &MATCH_END,
+        );
+    }
+    (client, admin, bettor)
+}
+
+fn spin_signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[42u8; 32])
+}
+
+fn backend_public_key(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &spin_signing_key().verifying_key().to_bytes())
+}
+
+fn sign_spin(env: &Env, spin_id: &BytesN<32>, spin_hash: &BytesN<32>) -> BytesN<64> {
+    sign_spin_with(env, &spin_signing_key(), spin_id, spin_hash)
+}
+
+fn sign_spin_with(
+    env: &Env,
+    key: &SigningKey,
+    spin_id: &BytesN<32>,
+    spin_hash: &BytesN<32>,
+) -> BytesN<64> {
+    let mut message = [0u8; 64];
+    message[..32].copy_from_slice(&spin_id.to_array());
+    message[32..].copy_from_slice(&spin_hash.to_array());
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+fn create_token<'a>(env: &Env) -> (Address, token::StellarAssetClient<'a>) {
+    let token_admin = Address::generate(env);
+    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
+    let token_id = token_contract.address();
+    let token_admin_client = token::StellarAssetClient::new(env, &token_id);
+    (token_id, token_admin_client)
+}
+
+/// Deposit `amount` of house liquidity from a fresh funder
+fn fund_house(
+    client: &BettingContractClient,
+    token_admin_client: &token::StellarAssetClient,
+    amount: i128,
+) {
+    let funder = Address::generate(&client.env);
+    token_admin_client.mint(&funder, &amount);
+    client.deposit(&funder, &token_admin_client.address, &amount);
+}
+
+#[test]
+fn test_place_bet_success() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, bettor) = setup_test(&env);
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract_v2(token_admin.clone());
+    let token_id = token_contract.address();
+    let token_client = token::Client::new(&env, &token_id);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let amount = 1000i128;
+    token_admin_client.mint(&bettor, &amount);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+    let odds = 20_000; // 2.00x
+
+    let result = client.try_place_bet(
+        &bettor, &token_id, &amount, &match_id, &bet_type, &odds, &None,
+    );
+    assert!(result.is_ok());
+
+    // Verify funds were transferred
+    assert_eq!(token_client.balance(&bettor), 0);
+    assert_eq!(token_client.balance(&client.address), amount);
+}
+
+#[test]
+fn house_fee_goes_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let treasury = Address::generate(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    client.set_fee_bps(&admin, &250);
+    client.set_treasury(&admin, &treasury);
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 20_000);
+
+    assert_eq!(token_client.balance(&treasury), 25);
+    assert_eq!(token_client.balance(&client.address), 975);
+    assert_eq!(client.get_escrow_balance(&token_id), 975);
+    let bet = client.get_bet(&match_id, &bettor);
+    assert_eq!(bet.amount, 975);
+    assert_eq!(
+        bet.metadata.get(Symbol::new(&env, "fee")),
+        Some(String::from_str(&env, "25"))
+    );
+    assert_eq!(client.get_global_exposure(), 1_950);
+}
+
+#[test]
+fn house_fee_is_taken_from_every_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    // Without a treasury, fees go to the admin
+    client.set_fee_bps(&admin, &1_000);
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    client.place_bets(
+        &bettor,
+        &token_id,
+        &vec![
+            &env,
+            (first.clone(), Symbol::new(&env, "win"), 600, 20_000),
+            (second.clone(), Symbol::new(&env, "draw"), 400, 30_000),
+        ],
+    );
+
+    assert_eq!(token_client.balance(&admin), 100);
+    assert_eq!(token_client.balance(&client.address), 900);
+    assert_eq!(client.get_bet(&first, &bettor).amount, 540);
+    assert_eq!(client.get_bet(&second, &bettor).amount, 360);
+}
+
+#[test]
+fn fee_must_be_below_whole_wager() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+
+    assert_eq!(
+        client.try_set_fee_bps(&admin, &10_000),
+        Err(Ok(ContractError::InvalidFee))
+    );
+    assert_eq!(
+        client.try_set_fee_bps(&bettor, &100),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_fee_bps(&admin, &9_999);
+    assert_eq!(client.get_fee_bps(), 9_999);
+}
+
+/// Token stand-in whose transfers always trap, as a hostile token might.
+#[contract]
+struct RejectingToken;
+
+#[contractimpl]
+impl RejectingToken {
+    pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {
+        panic!("transfer rejected");
+    }
+}
+
+#[test]
+fn rejected_stake_transfer_leaves_no_bet_behind() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, bettor) = setup_test(&env);
+    let token_id = env.register(RejectingToken, ());
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    let result = client.try_place_bet(
+        &bettor,
+        &token_id,
+        &1_000,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &20_000,
+        &None,
+    );
+    assert!(result.is_err());
+
+    assert_eq!(client.get_bettor_bets(&match_id, &bettor).len(), 0);
+    assert_eq!(client.get_match_bettors(&match_id).len(), 0);
+    assert_eq!(client.get_match_volume(&match_id), 0);
+    assert_eq!(client.get_global_exposure(), 0);
+    assert_eq!(client.get_token_exposure(&token_id), 0);
+}
+
+#[test]
+fn place_bets_escrows_every_leg_at_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    client.place_bets(
+        &bettor,
+        &token_id,
+        &vec![
+            &env,
+            (first.clone(), Symbol::new(&env, "win"), 300, 20_000),
+            (second.clone(), Symbol::new(&env, "draw"), 200, 30_000),
+        ],
+    );
+
+    assert_eq!(token_client.balance(&bettor), 500);
+    assert_eq!(token_client.balance(&client.address), 500);
+    assert_eq!(client.get_bet(&first, &bettor).amount, 300);
+    assert_eq!(client.get_bet(&second, &bettor).odds, 30_000);
+    assert_eq!(
+        client.get_bettor_matches(&bettor),
+        vec![&env, first, second]
+    );
+    assert_eq!(client.get_global_exposure(), 1_200);
+}
+
+#[test]
+fn place_bets_rejects_whole_batch_on_one_bad_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    assert_eq!(
+        client.try_place_bets(
+            &bettor,
+            &token_id,
+            &vec![
+                &env,
+                (first.clone(), Symbol::new(&env, "win"), 300, 20_000),
+                (second.clone(), Symbol::new(&env, "win"), 0, 20_000),
+            ],
+        ),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_place_bets(&bettor, &token_id, &vec![&env]),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+
+    assert_eq!(token_client.balance(&bettor), 1_000);
+    assert_eq!(client.get_bettor_bets(&first, &bettor).len(), 0);
+    assert_eq!(client.get_match_volume(&first), 0);
+    assert_eq!(client.get_bettor_matches(&bettor).len(), 0);
+}
+
+#[test]
+fn total_bettors_counts_each_address_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, first) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let second = Address::generate(&env);
+    assert_eq!(client.total_bettors(), 0);
+
+    for bettor in [&first, &second] {
+        token_admin_client.mint(bettor, &1_000);
+        place_test_bet(&env, &client, &token_id, bettor, 1, 100, 20_000);
+        place_test_bet(&env, &client, &token_id, bettor, 2, 100, 20_000);
+    }
+
+    assert_eq!(client.total_bettors(), 2);
+}
+
+#[test]
+fn test_prevent_double_betting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
+    let token_id = token_contract.address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let amount = 1000i128;
+    token_admin_client.mint(&bettor, &(amount * 2));
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+    let odds = 200;
+
+    // Enable double betting prevention
+    client.set_prevent_double_betting(&admin, &true);
+    assert!(client.is_double_betting_prevented());
+
+    // First bet
+    client.place_bet(
+        &bettor, &token_id, &amount, &match_id, &bet_type, &odds, &None,
+    );
+
+    // Second bet on same match by same user should fail
+    let result = client.try_place_bet(
+        &bettor, &token_id, &amount, &match_id, &bet_type, &odds, &None,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_allow_double_betting_when_disabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, bettor) = setup_test(&env);
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
+    let token_id = token_contract.address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let amount = 1000i128;
+    token_admin_client.mint(&bettor, &(amount * 2));
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+    let odds = 200;
+
+    // Ensure double betting is allowed (default)
+    assert!(!client.is_double_betting_prevented());
+
+    // First bet
+    client.place_bet(
+        &bettor, &token_id, &amount, &match_id, &bet_type, &odds, &None,
+    );
+
+    // Second bet on same match by same user should succeed
+    let result = client.try_place_bet(
+        &bettor, &token_id, &amount, &match_id, &bet_type, &odds, &None,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn bets_only_accepted_on_registered_open_matches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let place = |match_id: &BytesN<32>| {
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &100,
+            match_id,
+            &Symbol::new(&env, "win"),
+            &20_000,
+            &None,
+        )
+    };
+
+    let unknown = BytesN::from_array(&env, &[0xaa; 32]);
+    assert_eq!(place(&unknown), Err(Ok(ContractError::MatchNotFound)));
+    assert_eq!(
+        client.try_get_match(&unknown),
+        Err(Ok(ContractError::MatchNotFound))
+    );
+
+    assert_eq!(
+        client.try_create_match(&admin, &unknown, &500, &400),
+        Err(Ok(ContractError::InvalidMatchWindow))
+    );
+    client.create_match(&admin, &unknown, &500, &900);
+    assert_eq!(
+        client.get_match(&unknown),
+        Match {
+            match_id: unknown.clone(),
+            start_time: 500,
+            end_time: 900,
+        }
+    );
+    assert_eq!(
+        client.try_create_match(&admin, &unknown, &600, &900),
+        Err(Ok(ContractError::MatchAlreadyExists))
+    );
+
+    env.ledger().set_timestamp(499);
+    assert!(place(&unknown).is_ok());
+
+    // Betting closes once the match starts
+    env.ledger().set_timestamp(500);
+    assert_eq!(place(&unknown), Err(Ok(ContractError::MatchClosed)));
+}
+
+#[test]
+fn blocked_address_cannot_bet_until_unblocked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1000);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+
+    client.block_address(&admin, &bettor);
+    assert!(client.is_blocked(&bettor));
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &500, &match_id, &bet_type, &200, &None),
+        Err(Ok(ContractError::AddressBlocked))
+    );
+
+    client.unblock_address(&admin, &bettor);
+    assert!(!client.is_blocked(&bettor));
+    client.place_bet(&bettor, &token_id, &500, &match_id, &bet_type, &200, &None);
+}
+
+#[test]
+fn blocked_executor_cannot_spin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, executor) = setup_test(&env);
+    client.block_address(&admin, &executor);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+
+    assert_eq!(
+        client.try_execute_spin(
+            &spin_id,
+            &spin_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &executor,
+            &0
+        ),
+        Err(Ok(ContractError::AddressBlocked))
+    );
+    assert!(!client.is_spin_executed(&spin_id));
+}
+
+#[test]
+fn blocked_winner_cannot_claim_until_unblocked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+
+    client.block_address(&admin, &bettor);
+    assert_eq!(
+        client.try_claim(&bettor, &token_id),
+        Err(Ok(ContractError::AddressBlocked))
+    );
+    assert_eq!(client.get_claimable(&bettor, &token_id), 1_500);
+
+    client.unblock_address(&admin, &bettor);
+    assert_eq!(client.claim(&bettor, &token_id), 1_500);
+}
+
+#[test]
+fn only_admin_can_block() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, stranger) = setup_test(&env);
+
+    assert_eq!(
+        client.try_block_address(&stranger, &stranger),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn global_exposure_cap_rejects_bets_across_matches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &10_000);
+
+    let bet_type = Symbol::new(&env, "win");
+    let odds = 20_000; // 2.0x
+
+    client.set_global_exposure_cap(&admin, &3_000);
+    assert_eq!(client.get_global_exposure_cap(), Some(3_000));
+
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &1_000,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &bet_type,
+        &odds,
+        &None,
+    );
+    assert_eq!(client.get_global_exposure(), 2_000);
+
+    // A different match still counts against the same global cap
+    assert_eq!(
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &1_000,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &bet_type,
+            &odds,
+            &None
+        ),
+        Err(Ok(ContractError::ExposureLimitExceeded))
+    );
+
+    // Filling the cap exactly is allowed
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &500,
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &bet_type,
+        &odds,
+        &None,
+    );
+    assert_eq!(client.get_global_exposure(), 3_000);
+}
+
+// ============================================
+// Settlement
+// ============================================
+
+fn place_test_bet(
+    env: &Env,
+    client: &BettingContractClient,
+    token_id: &Address,
+    bettor: &Address,
+    seed: u8,
+    amount: i128,
+    odds: u32,
+) -> BytesN<32> {
+    let match_id = BytesN::from_array(env, &[seed; 32]);
+    client.place_bet(
+        bettor,
+        token_id,
+        &amount,
+        &match_id,
+        &Symbol::new(env, "win"),
+        &odds,
+        &None,
+    );
+    match_id
+}
+
+#[test]
+fn settle_winning_bet_credits_odds_from_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+    // House bankroll for the winnings
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    assert_eq!(client.get_global_exposure(), 1_500);
+
+    env.ledger().set_timestamp(MATCH_END);
+    let payout = client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+
+    let event = SettlementEvent {
+        bet_id: U256::from_be_bytes(&env, &match_id.clone().into()),
+        winner: bettor.clone(),
+        payout: 1_500,
+        betting_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+        settlement_type: Symbol::new(&env, "WIN"),
+        final_odds: 15_000,
+        metadata: Map::new(&env),
+    };
+    let available = WinningsAvailableEvent {
+        bettor: bettor.clone(),
+        match_id: match_id.clone(),
+        amount: 1_500,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (SETTLEMENT_EVENT, match_id).into_val(&env),
+                event.into_val(&env),
+            ),
+            (
+                client.address.clone(),
+                (WINNINGS_AVAILABLE_EVENT, bettor.clone()).into_val(&env),
+                available.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(payout, 1_500);
+    assert_eq!(client.get_claimable(&bettor, &token_id), 1_500);
+    assert_eq!(token_client.balance(&bettor), 0);
+    assert_eq!(client.get_global_exposure(), 0);
+    assert_eq!(client.get_token_exposure(&token_id), 0);
+
+    assert_eq!(client.claim(&bettor, &token_id), 1_500);
+    assert_eq!(client.get_claimable(&bettor, &token_id), 0);
+    assert_eq!(token_client.balance(&bettor), 1_500);
+    assert_eq!(token_client.balance(&client.address), 500);
+}
+
+#[test]
+fn claim_with_nothing_owed_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    assert_eq!(
+        client.try_claim(&bettor, &token_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+    assert_eq!(
+        client.try_claim(&bettor, &token_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn unclaimed_winnings_cannot_be_withdrawn() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+
+    // 2_000 held, 1_500 of it owed to the bettor
+    assert_eq!(
+        client.try_withdraw(&admin, &token_id, &admin, &501),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    client.withdraw(&admin, &token_id, &admin, &500);
+    assert_eq!(client.claim(&bettor, &token_id), 1_500);
+}
+
+#[test]
+fn active_stakes_cannot_be_withdrawn() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    fund_house(&client, &token_admin_client, 300);
+
+    place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+
+    // 1_300 held, 1_000 of it the open bet's stake
+    assert_eq!(
+        client.try_withdraw(&admin, &token_id, &admin, &301),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    client.withdraw(&admin, &token_id, &admin, &300);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    client.cancel_bet(&bettor, &match_id);
+    assert_eq!(token::Client::new(&env, &token_id).balance(&bettor), 1_000);
+}
+
+#[test]
+fn escrow_ledger_tracks_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    token_admin_client.mint(&loser, &400);
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    client.place_bet(
+        &loser,
+        &token_id,
+        &400,
+        &match_id,
+        &Symbol::new(&env, "away"),
+        &20_000,
+        &None,
+    );
+    assert_eq!(client.get_escrow_balance(&token_id), 2_400);
+    assert_eq!(token_client.balance(&client.address), 2_400);
+
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+    client.settle_bet(&admin, &match_id, &loser, &BetOutcome::Lose);
+    client.claim(&bettor, &token_id);
+    assert_eq!(client.get_escrow_balance(&token_id), 900);
+    assert_eq!(token_client.balance(&client.address), 900);
+
+    client.withdraw(&admin, &token_id, &admin, &900);
+    assert_eq!(client.get_escrow_balance(&token_id), 0);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn payouts_never_exceed_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    // Sent straight to the contract rather than deposited, so not escrowed
+    token_admin_client.mint(&client.address, &5_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+
+    assert_eq!(
+        client.try_claim(&bettor, &token_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_withdraw(&admin, &token_id, &admin, &1),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    fund_house(&client, &token_admin_client, 500);
+    assert_eq!(client.claim(&bettor, &token_id), 1_500);
+    assert_eq!(client.get_escrow_balance(&token_id), 0);
+}
+
+#[test]
+fn settlement_pays_in_the_escrowed_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (staked_token, staked_admin) = create_token(&env);
+    let (other_token, other_admin) = create_token(&env);
+    staked_admin.mint(&bettor, &1_000);
+    fund_house(&client, &staked_admin, 1_000);
+    fund_house(&client, &other_admin, 5_000);
+
+    let match_id = place_test_bet(&env, &client, &staked_token, &bettor, 1, 1_000, 20_000);
+    assert_eq!(
+        client.get_bet(&match_id, &bettor).token_address,
+        staked_token
+    );
+
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+    assert_eq!(
+        client.try_claim(&bettor, &other_token),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    client.claim(&bettor, &staked_token);
+
+    assert_eq!(
+        token::Client::new(&env, &staked_token).balance(&bettor),
+        2_000
+    );
+    assert_eq!(token::Client::new(&env, &other_token).balance(&bettor), 0);
+    assert_eq!(
+        token::Client::new(&env, &other_token).balance(&client.address),
+        5_000
+    );
+}
+
+#[test]
+fn draw_returns_the_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 30_000);
+    env.ledger().set_timestamp(MATCH_END);
+    let payout = client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Draw);
+
+    let event = SettlementEvent {
+        bet_id: U256::from_be_bytes(&env, &match_id.clone().into()),
+        winner: bettor.clone(),
+        payout: 1_000,
+        betting_contract: client.address.clone(),
+        timestamp: MATCH_END,
+        settlement_type: Symbol::new(&env, "DRAW"),
+        final_odds: 30_000,
+        metadata: Map::new(&env),
+    };
+    let available = WinningsAvailableEvent {
+        bettor: bettor.clone(),
+        match_id: match_id.clone(),
+        amount: 1_000,
+        timestamp: MATCH_END,
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (SETTLEMENT_EVENT, match_id.clone()).into_val(&env),
+                event.into_val(&env),
+            ),
+            (
+                client.address.clone(),
+                (WINNINGS_AVAILABLE_EVENT, bettor.clone()).into_val(&env),
+                available.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(payout, 1_000);
+    assert_eq!(
+        client.get_bet(&match_id, &bettor).status,
+        BetStatus::Settled
+    );
+    assert_eq!(client.get_global_exposure(), 0);
+    client.claim(&bettor, &token_id);
+    assert_eq!(token_client.balance(&bettor), 1_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn draw_does_not_return_the_house_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+    client.set_fee_bps(&admin, &500);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 30_000);
+    env.ledger().set_timestamp(MATCH_END);
+    assert_eq!(
+        client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Draw),
+        950
+    );
+    client.claim(&bettor, &token_id);
+    assert_eq!(token_client.balance(&bettor), 950);
+    assert_eq!(token_client.balance(&admin), 50);
+}
+
+#[test]
+fn settle_losing_bet_keeps_stake_escrowed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+
+    env.ledger().set_timestamp(MATCH_END);
+    let payout = client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+    assert_eq!(payout, 0);
+    assert_eq!(token_client.balance(&bettor), 0);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+    assert_eq!(client.get_global_exposure(), 0);
+}
+
+#[test]
+fn bet_status_moves_from_active_to_settled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 10_000);
+    let bet = client.get_bet(&match_id, &bettor);
+    assert_eq!(bet.status, BetStatus::Active);
+    assert_eq!(bet.amount, 1_000);
+
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+    assert_eq!(
+        client.get_bet(&match_id, &bettor).status,
+        BetStatus::Settled
+    );
+    assert_eq!(
+        client.try_get_bet(&BytesN::from_array(&env, &[9u8; 32]), &bettor),
+        Err(Ok(ContractError::BetNotFound))
+    );
+}
+
+#[test]
+fn settlement_waits_for_match_end() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 10_000);
+
+    env.ledger().set_timestamp(MATCH_END - 1);
+    assert_eq!(
+        client.try_settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win),
+        Err(Ok(ContractError::MatchNotEnded))
+    );
+    assert_eq!(client.get_bet(&match_id, &bettor).status, BetStatus::Active);
+
+    env.ledger().set_timestamp(MATCH_END);
+    assert_eq!(
+        client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win),
+        1_000
+    );
+}
+
+#[test]
+fn settle_match_settles_every_open_bet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    fund_house(&client, &token_admin_client, 10_000);
+
+    for bettor in [&first, &second, &third] {
+        token_admin_client.mint(bettor, &1_000);
+        place_test_bet(&env, &client, &token_id, bettor, 4, 500, 20_000);
+    }
+    let match_id = place_test_bet(&env, &client, &token_id, &first, 4, 100, 20_000);
+
+    // Already-closed bets are skipped rather than failing the batch
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &third, &BetOutcome::Lose);
+
+    assert_eq!(client.settle_match(&admin, &match_id, &BetOutcome::Win), 3);
+
+    let settlement = |winner: &Address, payout: i128| {
+        (
+            client.address.clone(),
+            (SETTLEMENT_EVENT, match_id.clone()).into_val(&env),
+            SettlementEvent {
+                bet_id: U256::from_be_bytes(&env, &match_id.clone().into()),
+                winner: winner.clone(),
+                payout,
+                betting_contract: client.address.clone(),
+                timestamp: MATCH_END,
+                settlement_type: Symbol::new(&env, "WIN"),
+                final_odds: 20_000,
+                metadata: Map::new(&env),
+            }
+            .into_val(&env),
+        )
+    };
+    let available = |winner: &Address, amount: i128| {
+        (
+            client.address.clone(),
+            (WINNINGS_AVAILABLE_EVENT, winner.clone()).into_val(&env),
+            WinningsAvailableEvent {
+                bettor: winner.clone(),
+                match_id: match_id.clone(),
+                amount,
+                timestamp: MATCH_END,
+            }
+            .into_val(&env),
+        )
+    };
+    let summary = MatchSettledEvent {
+        match_id: match_id.clone(),
+        winning_outcome: Symbol::new(&env, "WIN"),
+        total_bets: 3,
+        total_staked: 1_100,
+        total_payout: 2_200,
+        house_profit: -1_100,
+        timestamp: MATCH_END,
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            settlement(&first, 1_000),
+            available(&first, 1_000),
+            settlement(&first, 200),
+            available(&first, 200),
+            settlement(&second, 1_000),
+            available(&second, 1_000),
+            (
+                client.address.clone(),
+                (MATCH_SETTLED_EVENT, match_id.clone()).into_val(&env),
+                summary.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.claim(&first, &token_id), 1_200);
+    assert_eq!(client.claim(&second, &token_id), 1_000);
+    assert_eq!(token_client.balance(&first), 400 + 1_200);
+    assert_eq!(token_client.balance(&second), 500 + 1_000);
+    assert_eq!(token_client.balance(&third), 500);
+    assert!(client
+        .get_match_bets(&match_id)
+        .iter()
+        .all(|bet| bet.status == BetStatus::Settled));
+    assert_eq!(client.settle_match(&admin, &match_id, &BetOutcome::Win), 0);
+}
+
+#[test]
+fn bet_cannot_be_settled_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 10_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+
+    assert_eq!(
+        client.try_settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win,),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+    assert_eq!(
+        client.try_settle_bet(
+            &admin,
+            &BytesN::from_array(&env, &[9u8; 32]),
+            &bettor,
+            &BetOutcome::Win,
+        ),
+        Err(Ok(ContractError::BetNotFound))
+    );
+}
+
+#[test]
+fn only_admin_can_settle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 10_000);
+    assert_eq!(
+        client.try_settle_bet(&bettor, &match_id, &bettor, &BetOutcome::Win),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn house_withdraws_lost_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let treasury = Address::generate(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 400, 20_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+
+    assert_eq!(
+        client.try_withdraw(&admin, &token_id, &treasury, &401),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_withdraw(&bettor, &token_id, &bettor, &400),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.withdraw(&admin, &token_id, &treasury, &400);
+    assert_eq!(token_client.balance(&treasury), 400);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(token_client.balance(&bettor), 600);
+}
+
+#[test]
+fn place_bet_publishes_shared_topic() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 400, 20_000);
+
+    let event = BetEvent {
+        bettor: bettor.clone(),
+        amount: 400,
+        bet_id: U256::from_be_bytes(&env, &Bytes::from(match_id.clone())),
+        betting_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+        bet_type: Symbol::new(&env, "win"),
+        odds: 20_000,
+        metadata: Map::new(&env),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (BET_EVENT, match_id).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn bet_event_carries_odds_and_bet_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(MATCH_START - 1);
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = BytesN::from_array(&env, &[2u8; 32]);
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &250,
+        &match_id,
+        &Symbol::new(&env, "draw"),
+        &32_500,
+        &None,
+    );
+
+    let event = BetEvent {
+        bettor: bettor.clone(),
+        amount: 250,
+        bet_id: U256::from_be_bytes(&env, &Bytes::from(match_id.clone())),
+        betting_contract: client.address.clone(),
+        timestamp: MATCH_START - 1,
+        bet_type: Symbol::new(&env, "draw"),
+        odds: 32_500,
+        metadata: Map::new(&env),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (BET_EVENT, match_id).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn bet_metadata_is_stored_and_published() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let mut metadata = Map::new(&env);
+    metadata.set(
+        Symbol::new(&env, "selection"),
+        String::from_str(&env, "Home"),
+    );
+    metadata.set(
+        Symbol::new(&env, "market"),
+        String::from_str(&env, "match_result"),
+    );
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &400,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &20_000,
+        &Some(metadata.clone()),
+    );
+
+    let event = BetEvent {
+        bettor: bettor.clone(),
+        amount: 400,
+        bet_id: U256::from_be_bytes(&env, &Bytes::from(match_id.clone())),
+        betting_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+        bet_type: Symbol::new(&env, "win"),
+        odds: 20_000,
+        metadata: metadata.clone(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (BET_EVENT, match_id.clone()).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+
+    let bet = client.get_bet(&match_id, &bettor);
+    assert_eq!(bet.metadata.len(), 2);
+    assert_eq!(bet.metadata, metadata);
+}
+
+#[test]
+fn cancel_bet_refunds_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 400, 20_000);
+    assert_eq!(token_client.balance(&bettor), 600);
+
+    client.cancel_bet(&bettor, &match_id);
+
+    let event = BetCancelledEvent {
+        bettor: bettor.clone(),
+        match_id: match_id.clone(),
+        amount: 400,
+        reason: Symbol::new(&env, "bettor_cancelled"),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (BET_CANCELLED_EVENT, match_id.clone()).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(token_client.balance(&bettor), 1_000);
+    assert_eq!(client.get_global_exposure(), 0);
+    assert_eq!(
+        client.get_bet(&match_id, &bettor).status,
+        BetStatus::Cancelled
+    );
+    assert_eq!(
+        client.try_cancel_bet(&bettor, &match_id),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn cancel_penalty_grows_closer_to_match_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &3_000);
+
+    let tiers = vec![&env, (600u64, 500u32), (100u64, 2_000u32)];
+    assert_eq!(
+        client.try_set_cancel_tiers(&bettor, &tiers),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_cancel_tiers(&admin, &vec![&env, (600u64, 10_001u32)]),
+        Err(Ok(ContractError::InvalidFee))
+    );
+    client.set_cancel_tiers(&admin, &tiers);
+    assert_eq!(client.get_cancel_tiers(), tiers);
+
+    let early = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 20_000);
+    let late = place_test_bet(&env, &client, &token_id, &bettor, 2, 1_000, 20_000);
+    let last_minute = place_test_bet(&env, &client, &token_id, &bettor, 3, 1_000, 20_000);
+
+    // More than 600 seconds out is free
+    env.ledger().set_timestamp(MATCH_START - 601);
+    client.cancel_bet(&bettor, &early);
+    assert_eq!(token_client.balance(&bettor), 1_000);
+
+    // Within 600 seconds costs 5%
+    env.ledger().set_timestamp(MATCH_START - 600);
+    client.cancel_bet(&bettor, &late);
+    assert_eq!(token_client.balance(&bettor), 1_950);
+
+    // Within 100 seconds the tighter 20% tier applies
+    env.ledger().set_timestamp(MATCH_START - 100);
+    client.cancel_bet(&bettor, &last_minute);
+    assert_eq!(token_client.balance(&bettor), 2_750);
+
+    // Penalties stay with the house and can be withdrawn like any profit
+    assert_eq!(token_client.balance(&client.address), 250);
+    assert_eq!(client.get_escrow_balance(&token_id), 250);
+    assert_eq!(client.get_open_stakes(&token_id), 0);
+}
+
+#[test]
+fn cancel_bet_closes_at_match_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 400, 20_000);
+
+    for now in [MATCH_START, MATCH_END + 1] {
+        env.ledger().set_timestamp(now);
+        assert_eq!(
+            client.try_cancel_bet(&bettor, &match_id),
+            Err(Ok(ContractError::MatchClosed))
+        );
+    }
+    assert_eq!(token_client.balance(&bettor), 600);
+    assert_eq!(client.get_bet(&match_id, &bettor).status, BetStatus::Active);
+}
+
+#[test]
+fn resettle_bet_corrects_unclaimed_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    env.ledger().set_timestamp(MATCH_END);
+    assert_eq!(
+        client.try_resettle_bet(&admin, &match_id, &bettor, &BetOutcome::Win),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+    assert_eq!(client.get_claimable(&bettor, &token_id), 0);
+
+    assert_eq!(
+        client.try_resettle_bet(&bettor, &match_id, &bettor, &BetOutcome::Win),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.resettle_bet(&admin, &match_id, &bettor, &BetOutcome::Win),
+        1_500
+    );
+    let event = BetResettledEvent {
+        bettor: bettor.clone(),
+        match_id: match_id.clone(),
+        previous_amount: 0,
+        amount: 1_500,
+        settlement_type: Symbol::new(&env, "WIN"),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (BET_RESETTLED_EVENT, match_id.clone()).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_claimable(&bettor, &token_id), 1_500);
+
+    client.resettle_bet(&admin, &match_id, &bettor, &BetOutcome::Draw);
+    assert_eq!(client.get_claimable(&bettor, &token_id), 1_000);
+
+    // Once the payout is claimed the settlement can no longer change
+    assert_eq!(client.claim(&bettor, &token_id), 1_000);
+    assert_eq!(
+        client.try_resettle_bet(&admin, &match_id, &bettor, &BetOutcome::Win),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn claims_within_interval_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &2_000);
+    fund_house(&client, &token_admin_client, 2_000);
+
+    assert_eq!(client.get_claim_interval(), 0);
+    assert_eq!(
+        client.try_set_claim_interval(&bettor, &600),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_claim_interval(&admin, &600);
+
+    let first = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    let second = place_test_bet(&env, &client, &token_id, &bettor, 2, 1_000, 15_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &first, &bettor, &BetOutcome::Win);
+    assert_eq!(client.claim(&bettor, &token_id), 1_500);
+
+    client.settle_bet(&admin, &second, &bettor, &BetOutcome::Win);
+    env.ledger().set_timestamp(MATCH_END + 599);
+    assert_eq!(
+        client.try_claim(&bettor, &token_id),
+        Err(Ok(ContractError::CooldownNotMet))
+    );
+
+    env.ledger().set_timestamp(MATCH_END + 600);
+    assert_eq!(client.claim(&bettor, &token_id), 1_500);
+}
+
+#[test]
+fn prune_match_removes_ended_match_without_open_bets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let empty = BytesN::from_array(&env, &[0u8; 32]);
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 20_000);
+
+    assert_eq!(
+        client.try_prune_match(&admin, &empty),
+        Err(Ok(ContractError::MatchNotEnded))
+    );
+
+    env.ledger().set_timestamp(MATCH_END);
+    assert_eq!(
+        client.try_prune_match(&bettor, &empty),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.prune_match(&admin, &empty);
+    assert_eq!(
+        client.try_get_match(&empty),
+        Err(Ok(ContractError::MatchNotFound))
+    );
+
+    // A match with an open bet stays until the bet is resolved
+    assert_eq!(
+        client.try_prune_match(&admin, &match_id),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+    client.prune_match(&admin, &match_id);
+    assert_eq!(
+        client.try_get_match(&match_id),
+        Err(Ok(ContractError::MatchNotFound))
+    );
+}
+
+#[test]
+fn expire_match_refunds_every_active_bet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let second = Address::generate(&env);
+    let settled = Address::generate(&env);
+
+    for bettor in [&first, &second, &settled] {
+        token_admin_client.mint(bettor, &1_000);
+    }
+    let match_id = place_test_bet(&env, &client, &token_id, &first, 1, 400, 20_000);
+    place_test_bet(&env, &client, &token_id, &first, 1, 100, 20_000);
+    place_test_bet(&env, &client, &token_id, &second, 1, 300, 20_000);
+    place_test_bet(&env, &client, &token_id, &settled, 1, 200, 20_000);
+
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &settled, &BetOutcome::Lose);
+
+    assert_eq!(
+        client.try_expire_match(&first, &match_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.expire_match(&admin, &match_id), 3);
+
+    let expired = |bettor: &Address, amount: i128| {
+        (
+            client.address.clone(),
+            (BET_CANCELLED_EVENT, match_id.clone()).into_val(&env),
+            BetCancelledEvent {
+                bettor: bettor.clone(),
+                match_id: match_id.clone(),
+                amount,
+                reason: Symbol::new(&env, "EXPIRED"),
+                timestamp: MATCH_END,
+            }
+            .into_val(&env),
+        )
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            expired(&first, 400),
+            expired(&first, 100),
+            expired(&second, 300),
+        ]
+    );
+
+    assert_eq!(token_client.balance(&first), 1_000);
+    assert_eq!(token_client.balance(&second), 1_000);
+    assert_eq!(token_client.balance(&settled), 800);
+    for bet in client.get_bettor_bets(&match_id, &first).iter() {
+        assert_eq!(bet.status, BetStatus::Cancelled);
+    }
+    assert_eq!(
+        client.get_bet(&match_id, &settled).status,
+        BetStatus::Settled
+    );
+    assert_eq!(client.get_match_volume(&match_id), 200);
+    assert_eq!(client.get_global_exposure(), 0);
+
+    // Nothing left to refund on a second pass
+    assert_eq!(client.expire_match(&admin, &match_id), 0);
+}
+
+#[test]
+fn bettor_matches_lists_each_match_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    assert_eq!(client.get_bettor_matches(&bettor).len(), 0);
+
+    let first = place_test_bet(&env, &client, &token_id, &bettor, 1, 100, 20_000);
+    let second = place_test_bet(&env, &client, &token_id, &bettor, 2, 100, 20_000);
+    place_test_bet(&env, &client, &token_id, &bettor, 1, 100, 20_000);
+
+    assert_eq!(
+        client.get_bettor_matches(&bettor),
+        vec![&env, first, second]
+    );
+    assert_eq!(client.get_bettor_matches(&Address::generate(&env)).len(), 0);
+}
+
+#[test]
+fn settled_bet_cannot_be_cancelled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 400, 20_000);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Lose);
+
+    // Settlement only happens after the match, when cancelling has closed
+    assert_eq!(
+        client.try_cancel_bet(&bettor, &match_id),
+        Err(Ok(ContractError::MatchClosed))
+    );
+    assert_eq!(
+        client.get_bet(&match_id, &bettor).status,
+        BetStatus::Settled
+    );
+}
+
+#[test]
+fn match_queries_list_every_bettor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, first) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    let match_id = BytesN::from_array(&env, &[7u8; 32]);
+    assert_eq!(client.get_match_bettors(&match_id).len(), 0);
+
+    for (bettor, amount) in [(&first, 100), (&second, 200), (&third, 300)] {
+        token_admin_client.mint(bettor, &1_000);
+        place_test_bet(&env, &client, &token_id, bettor, 7, amount, 20_000);
+    }
+    // Re-betting does not duplicate the bettor
+    place_test_bet(&env, &client, &token_id, &first, 7, 150, 20_000);
+
+    assert_eq!(
+        client.get_match_bettors(&match_id),
+        vec![&env, first.clone(), second.clone(), third.clone()]
+    );
+
+    let bets = client.get_match_bets(&match_id);
+    assert_eq!(bets.len(), 4);
+    assert_eq!(bets.get(0).unwrap().bettor, first);
+    assert_eq!(bets.get(0).unwrap().amount, 100);
+    assert_eq!(bets.get(1).unwrap().amount, 150);
+    assert_eq!(bets.get(2).unwrap().amount, 200);
+    assert_eq!(bets.get(3).unwrap().bettor, third);
+}
+
+#[test]
+fn repeat_bets_are_stored_separately() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin_client.mint(&bettor, &1_000);
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 200, 20_000);
+    place_test_bet(&env, &client, &token_id, &bettor, 1, 300, 15_000);
+
+    // Escrow is additive rather than the second bet replacing the first
+    assert_eq!(token_client.balance(&client.address), 1_500);
+    assert_eq!(client.get_global_exposure(), 400 + 450);
+
+    let bets = client.get_bettor_bets(&match_id, &bettor);
+    assert_eq!(bets.len(), 2);
+    assert_eq!(bets.get(0).unwrap().amount, 200);
+    assert_eq!(bets.get(1).unwrap().amount, 300);
+    assert_eq!(client.get_bet(&match_id, &bettor).amount, 300);
+    assert_eq!(client.effective_payout(&match_id, &bettor), 850);
+
+    env.ledger().set_timestamp(MATCH_END);
+    let payout = client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+    assert_eq!(payout, 850);
+    client.claim(&bettor, &token_id);
+    assert_eq!(token_client.balance(&bettor), 500 + 850);
+    assert_eq!(client.get_global_exposure(), 0);
+    assert_eq!(
+        client.try_settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win),
+        Err(Ok(ContractError::BetAlreadySettled))
+    );
+}
+
+#[test]
+fn double_betting_check_sees_every_bet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    client.set_prevent_double_betting(&admin, &true);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 200, 20_000);
+    let retry = client.try_place_bet(
+        &bettor,
+        &token_id,
+        &100,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &20_000,
+        &None,
+    );
+    assert_eq!(retry, Err(Ok(ContractError::BetAlreadyPlaced)));
+
+    // Once the open bet is withdrawn the bettor may bet again
+    client.cancel_bet(&bettor, &match_id);
+    place_test_bet(&env, &client, &token_id, &bettor, 1, 100, 20_000);
+    assert_eq!(client.get_bettor_bets(&match_id, &bettor).len(), 2);
+}
+
+#[test]
+fn match_volume_tracks_stakes_net_of_cancellations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let second = Address::generate(&env);
+    token_admin_client.mint(&first, &1_000);
+    token_admin_client.mint(&second, &1_000);
+
+    let match_id = place_test_bet(&env, &client, &token_id, &first, 3, 250, 20_000);
+    place_test_bet(&env, &client, &token_id, &second, 3, 400, 20_000);
+    assert_eq!(client.get_match_volume(&match_id), 650);
+
+    client.cancel_bet(&second, &match_id);
+    assert_eq!(client.get_match_volume(&match_id), 250);
+
+    // Settlement does not change what was wagered
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &first, &BetOutcome::Lose);
+    assert_eq!(client.get_match_volume(&match_id), 250);
+}
+
+#[test]
+fn bet_limits_bound_stake_size() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &10_000);
+
+    // No limits configured: any positive stake is accepted
+    assert_eq!(client.get_bet_limits(), None);
+    place_test_bet(&env, &client, &token_id, &bettor, 1, 1, 20_000);
+
+    assert_eq!(
+        client.try_set_bet_limits(&admin, &500, &100),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.set_bet_limits(&admin, &100, &1_000);
+    assert_eq!(client.get_bet_limits(), Some((100, 1_000)));
+
+    let place = |seed: u8, amount: i128| {
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &amount,
+            &BytesN::from_array(&env, &[seed; 32]),
+            &Symbol::new(&env, "win"),
+            &20_000,
+            &None,
+        )
+    };
+    assert_eq!(place(2, 99), Err(Ok(ContractError::BetBelowMinimum)));
+    assert_eq!(place(3, 1_001), Err(Ok(ContractError::BetAboveMaximum)));
+    assert!(place(4, 100).is_ok());
+    assert!(place(5, 1_000).is_ok());
+}
+
+#[test]
+fn daily_limit_caps_stake_per_rolling_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &10_000);
+
+    // A fixture far enough out to keep accepting bets for a couple of days
+    let match_id = BytesN::from_array(&env, &[20u8; 32]);
+    client.create_match(&admin, &match_id, &500_000, &600_000);
+
+    assert_eq!(
+        client.try_set_daily_limit(&bettor, &1_000),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_daily_limit(&admin, &1_000);
+    assert_eq!(client.get_daily_limit(), Some(1_000));
+
+    let place = |amount: i128| {
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &amount,
+            &match_id,
+            &Symbol::new(&env, "win"),
+            &20_000,
+            &None,
+        )
+    };
+
+    env.ledger().set_timestamp(100);
+    assert!(place(600).is_ok());
+    assert_eq!(place(500), Err(Ok(ContractError::DailyLimitExceeded)));
+    assert!(place(400).is_ok());
+
+    // The window is still open one second before it lapses
+    env.ledger().set_timestamp(100 + DAILY_WINDOW_SECONDS - 1);
+    assert_eq!(place(1), Err(Ok(ContractError::DailyLimitExceeded)));
+
+    env.ledger().set_timestamp(100 + DAILY_WINDOW_SECONDS);
+    assert!(place(1_000).is_ok());
+    assert_eq!(place(1), Err(Ok(ContractError::DailyLimitExceeded)));
+}
+
+#[test]
+fn daily_limit_counts_stake_before_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &2_000);
+
+    client.set_fee_bps(&admin, &250);
+    client.set_daily_limit(&admin, &1_000);
+    place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 20_000);
+
+    // Only 975 reached escrow, but the full 1_000 wagered uses up the limit
+    assert_eq!(client.get_escrow_balance(&token_id), 975);
+    assert_eq!(
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &10,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &Symbol::new(&env, "win"),
+            &20_000,
+            &None,
+        ),
+        Err(Ok(ContractError::DailyLimitExceeded))
+    );
+}
+
+#[test]
+fn place_bet_validates_odds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &10_000);
+
+    let place = |seed: u8, odds: u32| {
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &100,
+            &BytesN::from_array(&env, &[seed; 32]),
+            &Symbol::new(&env, "win"),
+            &odds,
+            &None,
+        )
+    };
+    assert_eq!(place(1, 0), Err(Ok(ContractError::InvalidOdds)));
+
+    client.set_max_odds(&admin, &100_000);
+    assert_eq!(client.get_max_odds(), Some(100_000));
+    assert_eq!(place(2, 100_001), Err(Ok(ContractError::InvalidOdds)));
+    assert!(place(3, 25_000).is_ok());
+    assert!(place(4, 100_000).is_ok());
+}
+
+#[test]
+fn effective_payout_reflects_locked_in_odds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.try_effective_payout(&match_id, &bettor),
+        Err(Ok(ContractError::BetNotFound))
+    );
+
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &250,
+        &match_id,
+        &Symbol::new(&env, "win"),
+        &15_000,
+        &None,
+    );
+    assert_eq!(client.effective_payout(&match_id, &bettor), 375);
+}
+
+#[test]
+fn preview_payout_matches_settlement_formula() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _) = setup_test(&env);
+
+    assert_eq!(client.preview_payout(&250, &15_000), 375);
+    assert_eq!(client.preview_payout(&250, &(ODDS_SCALE as u32)), 250);
+    assert_eq!(client.preview_payout(&3, &15_000), 4);
+    assert_eq!(
+        client.try_preview_payout(&(i128::MAX / 2), &20_000),
+        Err(Ok(ContractError::ArithmeticOverflow))
+    );
+    assert_eq!(
+        client.try_preview_payout(&0, &15_000),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_preview_payout(&250, &0),
+        Err(Ok(ContractError::InvalidOdds))
+    );
+}
+
+#[test]
+fn oversized_amounts_report_overflow_instead_of_trapping() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+    let (token_id, _) = create_token(&env);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &(i128::MAX / 2),
+            &match_id,
+            &Symbol::new(&env, "win"),
+            &20_000,
+            &None
+        ),
+        Err(Ok(ContractError::ArithmeticOverflow))
+    );
+    assert_eq!(client.get_match_volume(&match_id), 0);
+
+    let reward = SpinReward {
+        token_address: token_id.clone(),
+        reward_amount: i128::MAX / 2 + 1,
+        multiplier: 1,
+    };
+    let spin = |nonce: u64, seed: u8| {
+        let spin_id = BytesN::from_array(&env, &[seed; 32]);
+        let spin_hash = BytesN::from_array(&env, &[seed + 1; 32]);
+        client.try_execute_spin_with_reward(
+            &spin_id,
+            &spin_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &bettor,
+            &nonce,
+            &reward,
+        )
+    };
+    assert!(spin(0, 1).is_ok());
+    assert_eq!(spin(1, 3), Err(Ok(ContractError::ArithmeticOverflow)));
+    assert_eq!(
+        client.get_pending_reward(&bettor, &token_id),
+        i128::MAX / 2 + 1
+    );
+}
+
+#[test]
+fn house_counterparty_rejects_bets_beyond_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &10_000);
+    // House bankroll
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let bet_type = Symbol::new(&env, "win");
+    let odds = 20_000; // 2.0x
+
+    client.set_house_counterparty(&admin, &true);
+    assert!(client.is_house_counterparty());
+
+    // 1000 bankroll + 1000 stake covers the 2000 payout
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &1_000,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &bet_type,
+        &odds,
+        &None,
+    );
+    assert_eq!(client.get_token_exposure(&token_id), 2_000);
+
+    // 3000 held would not cover 4000 of liability
+    assert_eq!(
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &1_000,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &bet_type,
+            &odds,
+            &None
+        ),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    // Without the check the same bet is accepted
+    client.set_house_counterparty(&admin, &false);
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &1_000,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &bet_type,
+        &odds,
+        &None,
+    );
+    assert_eq!(client.get_token_exposure(&token_id), 4_000);
+}
+
+#[test]
+fn house_counterparty_sets_aside_unclaimed_winnings_and_open_stakes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &10_000);
+    fund_house(&client, &token_admin_client, 1_000);
+    client.set_house_counterparty(&admin, &true);
+
+    let bet_type = Symbol::new(&env, "win");
+    let settled_match = BytesN::from_array(&env, &[1u8; 32]);
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &500,
+        &settled_match,
+        &bet_type,
+        &20_000,
+        &None,
+    );
+    assert_eq!(client.get_open_stakes(&token_id), 500);
+
+    // The win is owed but left unclaimed in the contract
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_match(&admin, &settled_match, &BetOutcome::Win);
+    assert_eq!(client.get_open_stakes(&token_id), 0);
+    assert_eq!(client.get_claimable(&bettor, &token_id), 1_000);
+
+    let later_match = BytesN::from_array(&env, &[100u8; 32]);
+    client.create_match(
+        &admin,
+        &later_match,
+        &(MATCH_END + 1_000),
+        &(MATCH_END + 2_000),
+    );
+
+    // 1500 held plus the 1000 stake covers the 2000 payout, but only 500 of
+    // it is the house's once the 1000 owed and the stake are set aside
+    assert_eq!(
+        client.try_place_bet(
+            &bettor,
+            &token_id,
+            &1_000,
+            &later_match,
+            &bet_type,
+            &20_000,
+            &None,
+        ),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    client.place_bet(
+        &bettor,
+        &token_id,
+        &500,
+        &later_match,
+        &bet_type,
+        &20_000,
+        &None,
+    );
+    assert_eq!(client.get_open_stakes(&token_id), 500);
+}
+
+#[test]
+fn accepts_spin_signed_by_backend_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+    );
+    assert!(client.is_spin_executed(&spin_id));
+}
+
+#[test]
+fn spin_reward_is_credited_to_executor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let reward = SpinReward {
+        token_address: token_id.clone(),
+        reward_amount: 250,
+        multiplier: 5,
+    };
+
+    client.execute_spin_with_reward(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+        &reward,
+    );
+
+    let event = SpinRewardEvent {
+        user: executor.clone(),
+        reward_amount: 250,
+        token_address: token_id.clone(),
+        game_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+        spin_id: U256::from_be_bytes(&env, &spin_id.clone().into()),
+        reward_type: Symbol::new(&env, "token"),
+        multiplier: 5,
+        metadata: Map::new(&env),
+    };
+    let executed = SpinExecutedEvent {
+        spin_id: spin_id.clone(),
+        executor: executor.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (SPIN_EXECUTED_EVENT, executor.clone()).into_val(&env),
+                executed.into_val(&env),
+            ),
+            (
+                client.address.clone(),
+                (SPIN_REWARD_EVENT, executor.clone()).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+
+    assert!(client.is_spin_executed(&spin_id));
+    assert_eq!(client.get_pending_reward(&executor, &token_id), 250);
+    assert_eq!(token_client.balance(&executor), 0);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+}
+
+#[test]
+fn spin_rewards_accrue_until_claimed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    let reward = SpinReward {
+        token_address: token_id.clone(),
+        reward_amount: 250,
+        multiplier: 5,
+    };
+
+    for (nonce, seed) in [(0u64, 1u8), (1, 3)] {
+        let spin_id = BytesN::from_array(&env, &[seed; 32]);
+        let spin_hash = BytesN::from_array(&env, &[seed + 1; 32]);
+        client.execute_spin_with_reward(
+            &spin_id,
+            &spin_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &executor,
+            &nonce,
+            &reward,
+        );
+    }
+    assert_eq!(client.get_pending_reward(&executor, &token_id), 500);
+
+    // Rewards are only paid once the contract is funded
+    assert_eq!(
+        client.try_claim_rewards(&executor, &token_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    fund_house(&client, &token_admin_client, 1_000);
+    assert_eq!(client.claim_rewards(&executor, &token_id), 500);
+    assert_eq!(client.get_pending_reward(&executor, &token_id), 0);
+    assert_eq!(token_client.balance(&executor), 500);
+    assert_eq!(token_client.balance(&client.address), 500);
+
+    assert_eq!(client.claim_rewards(&executor, &token_id), 0);
+    assert_eq!(token_client.balance(&executor), 500);
+}
+
+#[test]
+fn spin_rewards_are_not_paid_from_bettor_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, executor) = setup_test(&env);
+    let bettor = Address::generate(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &2_000);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    client.execute_spin_with_reward(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+        &SpinReward {
+            token_address: token_id.clone(),
+            reward_amount: 500,
+            multiplier: 1,
+        },
+    );
+
+    // An open stake and unclaimed winnings are escrowed, but none of it is the house's
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 1_000, 15_000);
+    place_test_bet(&env, &client, &token_id, &bettor, 2, 1_000, 15_000);
+    fund_house(&client, &token_admin_client, 500);
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+    assert_eq!(client.get_escrow_balance(&token_id), 2_500);
+    assert_eq!(
+        client.try_claim_rewards(&executor, &token_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(client.get_pending_reward(&executor, &token_id), 500);
+
+    fund_house(&client, &token_admin_client, 500);
+    assert_eq!(client.claim_rewards(&executor, &token_id), 500);
+}
+
+#[test]
+fn test_mode_spin_reward_still_needs_backend_signer() {
+    let env = Env::default();
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(&env, &contract_id);
+    let executor = Address::generate(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+
+    client.initialize_test_mode(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &backend_public_key(&env),
+    );
+    env.mock_all_auths_allowing_non_root_auth();
+    fund_house(&client, &token_admin_client, 1_000);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let reward = SpinReward {
+        token_address: token_id.clone(),
+        reward_amount: 250,
+        multiplier: 1,
+    };
+    env.mock_auths(&[MockAuth {
+        address: &executor,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "execute_spin_with_reward",
+            args: (
+                spin_id.clone(),
+                spin_id.clone(),
+                BytesN::from_array(&env, &[0u8; 64]),
+                executor.clone(),
+                0u64,
+                reward.clone(),
+            )
+                .into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client
+        .try_execute_spin_with_reward(
+            &spin_id,
+            &spin_id,
+            &BytesN::from_array(&env, &[0u8; 64]),
+            &executor,
+            &0,
+            &reward,
+        )
+        .is_err());
+    assert_eq!(token::Client::new(&env, &token_id).balance(&executor), 0);
+}
+
+#[test]
+fn rejects_tampered_spin_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+
+    let mut tampered = sign_spin(&env, &spin_id, &spin_hash).to_array();
+    tampered[0] ^= 0xff;
+    assert_eq!(
+        client.try_execute_spin(
+            &spin_id,
+            &spin_hash,
+            &BytesN::from_array(&env, &tampered),
+            &executor,
+            &0,
+        ),
+        Err(Err(soroban_sdk::InvokeError::Abort))
+    );
+
+    // A genuine signature for a different spin doesn't carry over
+    let other_hash = BytesN::from_array(&env, &[3u8; 32]);
+    assert_eq!(
+        client.try_execute_spin(
+            &spin_id,
+            &other_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &executor,
+            &0,
+        ),
+        Err(Err(soroban_sdk::InvokeError::Abort))
+    );
+    assert!(!client.is_spin_executed(&spin_id));
+}
+
+#[test]
+fn executes_spin_once_per_spin_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+    );
+    assert_eq!(
+        client.try_execute_spin(
+            &spin_id,
+            &spin_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &executor,
+            &1
+        ),
+        Err(Ok(ContractError::SpinAlreadyExecuted))
+    );
+}
+
+#[test]
+fn rejects_reused_spin_nonce() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let first_spin = BytesN::from_array(&env, &[7u8; 32]);
+    let second_spin = BytesN::from_array(&env, &[8u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[9u8; 32]);
+
+    assert_eq!(client.get_nonce(&executor), 0);
+    client.execute_spin(
+        &first_spin,
+        &spin_hash,
+        &sign_spin(&env, &first_spin, &spin_hash),
+        &executor,
+        &0,
+    );
+    assert_eq!(client.get_nonce(&executor), 1);
+    assert!(client.is_spin_hash_used(&client.spin_replay_hash(&first_spin, &executor, &0)));
+
+    assert_eq!(
+        client.try_execute_spin(
+            &second_spin,
+            &spin_hash,
+            &sign_spin(&env, &second_spin, &spin_hash),
+            &executor,
+            &0,
+        ),
+        Err(Ok(ContractError::InvalidNonce))
+    );
+
+    // The next nonce goes through, even with a reused precomputed spin_hash
+    client.execute_spin(
+        &second_spin,
+        &spin_hash,
+        &sign_spin(&env, &second_spin, &spin_hash),
+        &executor,
+        &1,
+    );
+    assert!(client.is_spin_executed(&second_spin));
+    assert_eq!(client.get_nonce(&executor), 2);
+}
+
+#[test]
+fn reports_spin_hash_usage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[10u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[11u8; 32]);
+    let replay_hash = client.spin_replay_hash(&spin_id, &executor, &0);
+
+    assert!(!client.is_spin_hash_used(&replay_hash));
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+    );
+    assert!(client.is_spin_hash_used(&replay_hash));
+}
+
+#[test]
+fn supports_ttl_cleanup_for_spin_hashes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[12u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[13u8; 32]);
+    let replay_hash = client.spin_replay_hash(&spin_id, &executor, &0);
+
+    client.execute_spin_with_ttl(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+        &Some(5),
+    );
+    assert!(client.is_spin_hash_used(&replay_hash));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 6;
+    });
+
+    assert!(client.cleanup_spin_hash(&replay_hash));
+    assert!(!client.is_spin_hash_used(&replay_hash));
+}
+
+#[test]
+fn spin_executed_event_indexes_executor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[14u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[15u8; 32]);
+
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+    );
+
+    let event = SpinExecutedEvent {
+        spin_id,
+        executor: executor.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (SPIN_EXECUTED_EVENT, executor).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn executor_spins_are_listed_per_executor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+    let other = Address::generate(&env);
+
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    let elsewhere = BytesN::from_array(&env, &[3u8; 32]);
+    for (spin_id, caller, nonce) in [
+        (&first, &executor, 0u64),
+        (&elsewhere, &other, 0),
+        (&second, &executor, 1),
+    ] {
+        let spin_hash = BytesN::from_array(&env, &[spin_id.to_array()[0] + 100; 32]);
+        client.execute_spin(
+            spin_id,
+            &spin_hash,
+            &sign_spin(&env, spin_id, &spin_hash),
+            caller,
+            &nonce,
+        );
+    }
+
+    assert_eq!(
+        client.get_executor_spins(&executor),
+        vec![&env, first.clone(), second.clone()]
+    );
+    assert_eq!(client.get_executor_spins(&other), vec![&env, elsewhere]);
+
+    let (page, cursor) = client.list_executor_spins(&executor, &0, &1);
+    assert_eq!(page, vec![&env, first]);
+    assert_eq!(cursor, Some(1));
+    let (page, cursor) = client.list_executor_spins(&executor, &1, &5);
+    assert_eq!(page, vec![&env, second]);
+    assert_eq!(cursor, None);
+    let (page, cursor) = client.list_executor_spins(&executor, &7, &5);
+    assert_eq!(page.len(), 0);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn list_spin_executions_pages_through_all_spins() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, executor) = setup_test(&env);
+
+    for i in 0..5u8 {
+        let spin_id = BytesN::from_array(&env, &[i; 32]);
+        let spin_hash = BytesN::from_array(&env, &[100 + i; 32]);
+        client.execute_spin(
+            &spin_id,
+            &spin_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &executor,
+            &(i as u64),
+        );
+    }
+
+    let (first, cursor) = client.list_spin_executions(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(cursor, Some(2));
+
+    let (second, cursor) = client.list_spin_executions(&2, &2);
+    assert_eq!(second.len(), 2);
+    assert_eq!(cursor, Some(4));
+
+    let (last, cursor) = client.list_spin_executions(&4, &2);
+    assert_eq!(last.len(), 1);
+    assert_eq!(cursor, None);
+
+    let mut seen = Vec::new(&env);
+    for execution in first.iter().chain(second.iter()).chain(last.iter()) {
+        assert!(!seen.contains(&execution.spin_id));
+        seen.push_back(execution.spin_id);
+    }
+    for i in 0..5u8 {
+        assert!(seen.contains(BytesN::from_array(&env, &[i; 32])));
+    }
+
+    let (past_end, cursor) = client.list_spin_executions(&10, &2);
+    assert_eq!(past_end.len(), 0);
+    assert_eq!(cursor, None);
+}
+
+// ============================================
+// Global Pause
+// ============================================
+
+#[test]
+fn shared_pause_halts_betting_and_player_cards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // The betting contract acts as the shared incident switch
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let cards_id = env.register(player_card::PlayerCardContract, ());
+    let cards = player_card::PlayerCardContractClient::new(&env, &cards_id);
+    let cards_admin = Address::generate(&env);
+    cards.initialize(&cards_admin);
+    cards.set_global_pauser(&cards_admin, &client.address);
+    assert_eq!(cards.global_pauser(), Some(client.address.clone()));
+
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+    let uri = soroban_sdk::String::from_str(&env, "ipfs://card");
+
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &15_000, &None),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    assert_eq!(
+        cards.try_mint(&BytesN::from_array(&env, &[1u8; 32]), &bettor, &uri, &None),
+        Err(Ok(player_card::Error::ContractPaused))
+    );
+
+    client.set_paused(&admin, &false);
+    client.place_bet(
+        &bettor, &token_id, &100, &match_id, &bet_type, &15_000, &None,
+    );
+    cards.mint(&BytesN::from_array(&env, &[1u8; 32]), &bettor, &uri, &None);
+}
+
+#[test]
+fn paused_contract_rejects_writes_but_serves_reads() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &bettor,
+        &0,
+    );
+
+    client.set_paused(&admin, &true);
+    let match_id = BytesN::from_array(&env, &[1u8; 32]);
+    let bet_type = Symbol::new(&env, "win");
+    assert_eq!(
+        client.try_place_bet(&bettor, &token_id, &100, &match_id, &bet_type, &15_000, &None),
+        Err(Ok(ContractError::ContractPaused))
+    );
+
+    let next_spin = BytesN::from_array(&env, &[3u8; 32]);
+    let next_hash = BytesN::from_array(&env, &[4u8; 32]);
+    assert_eq!(
+        client.try_execute_spin(
+            &next_spin,
+            &next_hash,
+            &sign_spin(&env, &next_spin, &next_hash),
+            &bettor,
+            &0,
+        ),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    assert!(client.is_spin_executed(&spin_id));
+
+    client.set_paused(&admin, &false);
+    client.place_bet(
+        &bettor, &token_id, &100, &match_id, &bet_type, &15_000, &None,
+    );
+}
+
+#[test]
+fn global_pauser_halts_spins() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let (pauser, pauser_signer, _) = setup_test(&env);
+    let executor = Address::generate(&env);
+
+    client.set_global_pauser(&admin, &pauser.address);
+    pauser.set_paused(&pauser_signer, &true);
+
+    // Only the pauser's own flag is set
+    assert!(!client.is_paused());
+    assert_eq!(
+        client.try_execute_spin(
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &BytesN::from_array(&env, &[0u8; 64]),
+            &executor,
+            &0,
+        ),
+        Err(Ok(ContractError::ContractPaused))
+    );
+}
+
+#[test]
+fn only_admin_can_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, bettor) = setup_test(&env);
+
+    assert_eq!(
+        client.try_set_paused(&bettor, &true),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_global_pauser(&bettor, &bettor),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+// ============================================
+// Test Mode
+// ============================================
+
+fn mock_executor_only_spin(env: &Env, contract: &Address, executor: &Address, seed: u8) {
+    env.mock_auths(&[MockAuth {
+        address: executor,
+        invoke: &MockAuthInvoke {
+            contract,
+            fn_name: "execute_spin",
+            args: (
+                BytesN::from_array(env, &[seed; 32]),
+                BytesN::from_array(env, &[seed; 32]),
+                BytesN::from_array(env, &[0u8; 64]),
+                executor.clone(),
+                0u64,
+            )
+                .into_val(env),
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_mode_skips_backend_signer_auth() {
+    let env = Env::default();
     let contract_id = env.register(BettingContract, ());
-    let client = BettingContractClient::new(env, &contract_id);
-    let backend_signer = Address::generate(env);
-    let bettor = Address::generate(env);
-    client.initialize(&backend_signer);
-    (client, backend_signer, bettor)
+    let client = BettingContractClient::new(&env, &contract_id);
+    let executor = Address::generate(&env);
+
+    client.initialize_test_mode(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &backend_public_key(&env),
+    );
+    assert!(client.is_test_mode());
+
+    mock_executor_only_spin(&env, &contract_id, &executor, 1);
+    client.execute_spin(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &BytesN::from_array(&env, &[0u8; 64]),
+        &executor,
+        &0,
+    );
+    assert!(client.is_spin_executed(&BytesN::from_array(&env, &[1u8; 32])));
 }
 
 #[test]
-fn test_place_bet_success() {
+fn production_mode_requires_backend_signer_auth() {
+    let env = Env::default();
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(&env, &contract_id);
+    let executor = Address::generate(&env);
+
+    client.initialize(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &backend_public_key(&env),
+    );
+    assert!(!client.is_test_mode());
+
+    mock_executor_only_spin(&env, &contract_id, &executor, 1);
+    assert!(client
+        .try_execute_spin(
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &BytesN::from_array(&env, &[0u8; 64]),
+            &executor,
+            &0,
+        )
+        .is_err());
+}
+
+#[test]
+fn bets_and_spins_outlive_default_ttl() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _backend_signer, bettor) = setup_test(&env);
-    let token_admin = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract_v2(token_admin.clone());
-    let token_id = token_contract.address();
-    let token_client = token::Client::new(&env, &token_id);
-    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    token_admin_client.mint(&bettor, &1_000);
+    fund_house(&client, &token_admin_client, 1_000);
+    client.set_fee_bps(&admin, &100);
+    client.set_bet_limits(&admin, &1, &10_000);
 
-    let amount = 1000i128;
-    token_admin_client.mint(&bettor, &amount);
+    let match_id = place_test_bet(&env, &client, &token_id, &bettor, 1, 400, 20_000);
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &bettor,
+        &0,
+    );
+    client.bump_ttl(&admin, &vec![&env, token_id.clone()]);
+
+    let settings = [
+        DataKey::Admin,
+        DataKey::BackendSigner,
+        DataKey::BackendPublicKey,
+        DataKey::Initialized,
+        DataKey::FeeBps,
+        DataKey::BetLimits,
+        DataKey::EscrowBalance(token_id.clone()),
+        DataKey::GlobalExposure,
+        DataKey::TokenExposure(token_id.clone()),
+        DataKey::OpenStakes(token_id.clone()),
+        DataKey::SpinCount,
+    ];
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        for key in [
+            DataKey::Match(match_id.clone()),
+            DataKey::Bet(match_id.clone(), bettor.clone(), 0),
+            DataKey::BettorMatches(bettor.clone()),
+            DataKey::KnownBettor(bettor.clone()),
+            DataKey::TotalBettors,
+            DataKey::MatchVolume(match_id.clone()),
+            DataKey::SpinExecution(spin_id.clone()),
+            DataKey::SpinAt(0),
+            DataKey::ExecutorSpins(bettor.clone()),
+            DataKey::Nonce(bettor.clone()),
+        ]
+        .iter()
+        .chain(settings.iter())
+        {
+            assert_eq!(storage.get_ttl(key), common::TTL_EXTEND_TO);
+        }
+        assert_eq!(env.storage().instance().get_ttl(), common::TTL_EXTEND_TO);
+    });
 
-    let match_id = BytesN::from_array(&env, &[1u8; 32]);
-    let bet_type = Symbol::new(&env, "win");
-    let odds = 200; // 2.00
+    // Well past the default TTL but inside the extension
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 60 * common::DAY_IN_LEDGERS);
 
-    let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
-    assert!(result.is_ok());
+    assert_eq!(client.get_bet(&match_id, &bettor).amount, 396);
+    assert_eq!(
+        client.get_bettor_matches(&bettor),
+        vec![&env, match_id.clone()]
+    );
+    assert!(client.is_spin_executed(&spin_id));
+    assert_eq!(client.get_executor_spins(&bettor), vec![&env, spin_id]);
+    assert_eq!(client.get_nonce(&bettor), 1);
+
+    // Winnings are written with a fresh TTL
+    env.ledger().set_timestamp(MATCH_END);
+    client.settle_bet(&admin, &match_id, &bettor, &BetOutcome::Win);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        for key in [
+            DataKey::Bet(match_id.clone(), bettor.clone(), 0),
+            DataKey::Claimable(bettor.clone(), token_id.clone()),
+            DataKey::TotalClaimable(token_id.clone()),
+        ] {
+            assert_eq!(storage.get_ttl(&key), common::TTL_EXTEND_TO);
+        }
+    });
 
-    // Verify funds were transferred
-    assert_eq!(token_client.balance(&bettor), 0);
-    assert_eq!(token_client.balance(&client.address), amount);
+    // Settings nobody has touched since are brought back up by bump_ttl
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 10 * common::DAY_IN_LEDGERS);
+    client.bump_ttl(&admin, &vec![&env, token_id.clone()]);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get_ttl(&DataKey::Admin), common::TTL_EXTEND_TO);
+        assert_eq!(storage.get_ttl(&DataKey::FeeBps), common::TTL_EXTEND_TO);
+        for key in settings.iter() {
+            assert!(storage.get_ttl(key) > common::TTL_THRESHOLD);
+        }
+    });
 }
 
 #[test]
-fn test_prevent_double_betting() {
+fn bump_ttl_requires_admin() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, backend_signer, bettor) = setup_test(&env);
-    let token_admin = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
-    let token_id = token_contract.address();
-    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+    let (client, _, bettor) = setup_test(&env);
+    assert_eq!(
+        client.try_bump_ttl(&bettor, &vec![&env]),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
 
-    let amount = 1000i128;
-    token_admin_client.mint(&bettor, &(amount * 2));
+#[test]
+fn initialize_rejects_second_call() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    let match_id = BytesN::from_array(&env, &[1u8; 32]);
-    let bet_type = Symbol::new(&env, "win");
-    let odds = 200;
+    let (client, admin, _) = setup_test(&env);
+    let intruder = Address::generate(&env);
 
-    // Enable double betting prevention
-    client.set_prevent_double_betting(&backend_signer, &true);
-    assert!(client.is_double_betting_prevented());
+    assert_eq!(
+        client.try_initialize(&intruder, &intruder, &backend_public_key(&env)),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+    // The original admin is still in charge
+    client.set_max_odds(&admin, &50_000);
+    assert!(client.try_set_max_odds(&intruder, &50_000).is_err());
+}
 
-    // First bet
-    client.place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
+#[test]
+fn test_mode_cannot_be_enabled_after_init() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    // Second bet on same match by same user should fail
-    let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
-    assert!(result.is_err());
+    let (client, admin, _) = setup_test(&env);
+
+    assert_eq!(
+        client.try_initialize_test_mode(&admin, &admin, &backend_public_key(&env)),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+    assert!(!client.is_test_mode());
 }
 
+// ============================================
+// Schema Migration
+// ============================================
+
 #[test]
-fn test_allow_double_betting_when_disabled() {
+fn fresh_deployment_reports_current_schema_version() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, backend_signer, bettor) = setup_test(&env);
-    let token_admin = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract_v2(token_admin);
-    let token_id = token_contract.address();
-    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
-
-    let amount = 1000i128;
-    token_admin_client.mint(&bettor, &(amount * 2));
+    let (client, admin, _) = setup_test(&env);
 
-    let match_id = BytesN::from_array(&env, &[1u8; 32]);
-    let bet_type = Symbol::new(&env, "win");
-    let odds = 200;
+    assert_eq!(client.get_version(), SCHEMA_VERSION);
+    assert_eq!(
+        client.migrate(&admin, &Address::generate(&env), &vec![&env]),
+        SCHEMA_VERSION
+    );
+}
 
-    // Ensure double betting is allowed (default)
-    assert!(!client.is_double_betting_prevented());
+#[test]
+fn migrate_moves_legacy_spin_executions() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    // First bet
-    client.place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
+    let (client, admin, executor) = setup_test(&env);
+
+    let spin_id = BytesN::from_array(&env, &[21u8; 32]);
+    let execution = SpinExecution {
+        spin_id: spin_id.clone(),
+        executor: executor.clone(),
+        timestamp: 777,
+    };
+
+    // Write the record the way a schema v1 deployment stored it
+    env.as_contract(&client.address, || {
+        let mut legacy = Map::new(&env);
+        legacy.set(spin_id.clone(), execution.clone());
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::SpinExecutions, &legacy);
+        storage.remove(&DataKey::SchemaVersion);
+    });
 
-    // Second bet on same match by same user should succeed
-    let result = client.try_place_bet(&bettor, &token_id, &amount, &match_id, &bet_type, &odds);
-    assert!(result.is_ok());
+    assert_eq!(client.get_version(), 1);
+    assert!(!client.is_spin_executed(&spin_id));
+
+    let token = Address::generate(&env);
+    assert_eq!(client.migrate(&admin, &token, &vec![&env]), SCHEMA_VERSION);
+    assert_eq!(client.get_version(), SCHEMA_VERSION);
+    assert_eq!(client.get_spin_execution(&spin_id), execution);
+
+    // Second run is a no-op
+    assert_eq!(client.migrate(&admin, &token, &vec![&env]), SCHEMA_VERSION);
+    assert_eq!(client.get_spin_execution(&spin_id), execution);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::SpinExecutions));
+        assert_eq!(storage.get::<_, u32>(&DataKey::SpinCount), Some(1));
+    });
 }
 
 #[test]
-fn executes_spin_once_per_spin_id() {
+fn migrate_moves_legacy_bets_into_indexed_slots() {
     let env = Env::default();
     env.mock_all_auths();
+    env.ledger().set_timestamp(MATCH_START - 1);
+
+    let (client, admin, bettor) = setup_test(&env);
+    let (token_id, token_admin_client) = create_token(&env);
+    let match_id = BytesN::from_array(&env, &[3u8; 32]);
+    let legacy = BetV1 {
+        bettor: bettor.clone(),
+        amount: 100,
+        match_id: match_id.clone(),
+        bet_type: Symbol::new(&env, "home"),
+        odds: 20_000,
+        timestamp: 500,
+    };
+
+    // Write the bet the way a schema v2 deployment stored it, stake held
+    token_admin_client.mint(&client.address, &100);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(&legacy_bet_key(&env, &match_id, &bettor), &legacy);
+        storage.set(&DataKey::SchemaVersion, &2u32);
+    });
+    assert_eq!(client.get_bettor_bets(&match_id, &bettor).len(), 0);
+
+    let stranger = Address::generate(&env);
+    let legacy_bets = vec![
+        &env,
+        (match_id.clone(), bettor.clone()),
+        (match_id.clone(), stranger.clone()),
+    ];
+    assert_eq!(
+        client.migrate(&admin, &token_id, &legacy_bets),
+        SCHEMA_VERSION
+    );
 
-    let (client, _, executor) = setup_test(&env);
+    let bet = Bet {
+        bettor: bettor.clone(),
+        token_address: token_id.clone(),
+        amount: 100,
+        match_id: match_id.clone(),
+        bet_type: Symbol::new(&env, "home"),
+        odds: 20_000,
+        timestamp: 500,
+        status: BetStatus::Active,
+        metadata: Map::new(&env),
+    };
+    assert_eq!(client.get_bettor_bets(&match_id, &bettor), vec![&env, bet]);
+    assert_eq!(
+        client.get_match_bettors(&match_id),
+        vec![&env, bettor.clone()]
+    );
+    assert_eq!(client.get_match_volume(&match_id), 100);
+    assert_eq!(client.get_escrow_balance(&token_id), 100);
+    assert_eq!(client.get_token_exposure(&token_id), 200);
+    assert_eq!(client.get_open_stakes(&token_id), 100);
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&legacy_bet_key(&env, &match_id, &bettor)));
+    });
 
-    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
-    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    // The migrated bet settles and pays out like any other
+    fund_house(&client, &token_admin_client, 100);
+    env.ledger().set_timestamp(MATCH_END);
+    assert_eq!(client.settle_match(&admin, &match_id, &BetOutcome::Win), 1);
+    assert_eq!(client.claim(&bettor, &token_id), 200);
+}
+
+#[test]
+fn migrate_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, stranger) = setup_test(&env);
 
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
     assert_eq!(
-        client.try_execute_spin(&spin_id, &spin_hash, &signature, &executor),
-        Err(Ok(ContractError::DuplicateOperation))
+        client.try_migrate(&stranger, &Address::generate(&env), &vec![&env]),
+        Err(Ok(ContractError::Unauthorized))
     );
 }
 
 #[test]
-fn rejects_replay_by_spin_hash() {
+fn backend_signer_can_be_rotated() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _, executor) = setup_test(&env);
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let backend_signer = Address::generate(&env);
+    let new_signer = Address::generate(&env);
+    client.initialize(&admin, &backend_signer, &backend_public_key(&env));
 
-    let spin_hash = BytesN::from_array(&env, &[9u8; 32]);
-    let signature = BytesN::from_array(&env, &[4u8; 64]);
+    let new_key = SigningKey::from_bytes(&[7u8; 32]);
+    let new_public_key = BytesN::from_array(&env, &new_key.verifying_key().to_bytes());
+
+    // Not even the admin can take over the signer role
+    assert_eq!(
+        client.try_set_backend_signer(&admin, &admin, &new_public_key),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.set_backend_signer(&backend_signer, &new_signer, &new_public_key);
+    assert_eq!(
+        client.try_set_backend_signer(&backend_signer, &backend_signer, &new_public_key),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Spins signed with the retired key no longer verify
+    let executor = Address::generate(&env);
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    assert!(client
+        .try_execute_spin(
+            &spin_id,
+            &spin_hash,
+            &sign_spin(&env, &spin_id, &spin_hash),
+            &executor,
+            &0,
+        )
+        .is_err());
+    assert!(!client.is_spin_executed(&spin_id));
 
     client.execute_spin(
-        &BytesN::from_array(&env, &[7u8; 32]),
+        &spin_id,
         &spin_hash,
-        &signature,
+        &sign_spin_with(&env, &new_key, &spin_id, &spin_hash),
         &executor,
+        &0,
+    );
+    assert!(client.is_spin_executed(&spin_id));
+
+    client.set_backend_signer(&new_signer, &backend_signer, &backend_public_key(&env));
+}
+
+#[test]
+fn config_reports_every_setting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    let treasury = Address::generate(&env);
+
+    let config = client.get_config();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.treasury, admin);
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.min_amount, None);
+    assert_eq!(config.max_odds, None);
+    assert!(!config.paused);
+
+    client.set_fee_bps(&admin, &300);
+    client.set_treasury(&admin, &treasury);
+    client.set_bet_limits(&admin, &10, &5_000);
+    client.set_max_odds(&admin, &50_000);
+    client.set_daily_limit(&admin, &20_000);
+    client.set_global_exposure_cap(&admin, &1_000_000);
+    client.set_prevent_double_betting(&admin, &true);
+    client.set_house_counterparty(&admin, &true);
+    client.set_paused(&admin, &true);
+
+    assert_eq!(
+        client.get_config(),
+        Config {
+            admin,
+            treasury,
+            fee_bps: 300,
+            min_amount: Some(10),
+            max_amount: Some(5_000),
+            max_odds: Some(50_000),
+            daily_limit: Some(20_000),
+            global_exposure_cap: Some(1_000_000),
+            prevent_double_betting: true,
+            house_counterparty: true,
+            paused: true,
+            global_pauser: None,
+            test_mode: false,
+        }
     );
+}
 
+#[test]
+fn config_requires_initialization() {
+    let env = Env::default();
+    let client = BettingContractClient::new(&env, &env.register(BettingContract, ()));
     assert_eq!(
-        client.try_execute_spin(
-            &BytesN::from_array(&env, &[8u8; 32]),
-            &spin_hash,
-            &signature,
-            &executor,
-        ),
-        Err(Ok(ContractError::DuplicateOperation))
+        client.try_get_config(),
+        Err(Ok(ContractError::NotInitialized))
     );
 }
 
 #[test]
-fn reports_spin_hash_usage() {
+fn roles_can_be_read_back() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _, executor) = setup_test(&env);
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_get_backend_signer(),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_get_admin(),
+        Err(Ok(ContractError::NotInitialized))
+    );
 
-    let spin_id = BytesN::from_array(&env, &[10u8; 32]);
-    let spin_hash = BytesN::from_array(&env, &[11u8; 32]);
-    let signature = BytesN::from_array(&env, &[5u8; 64]);
+    let admin = Address::generate(&env);
+    let backend_signer = Address::generate(&env);
+    client.initialize(&admin, &backend_signer, &backend_public_key(&env));
+    assert_eq!(client.get_backend_signer(), backend_signer);
+    assert_eq!(client.get_admin(), admin);
 
-    assert!(!client.is_spin_hash_used(&spin_hash));
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
-    assert!(client.is_spin_hash_used(&spin_hash));
+    let new_signer = Address::generate(&env);
+    client.set_backend_signer(&backend_signer, &new_signer, &backend_public_key(&env));
+    assert_eq!(client.get_backend_signer(), new_signer);
 }
 
 #[test]
-fn supports_ttl_cleanup_for_spin_hashes() {
+fn backend_signer_cannot_change_settings() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _, executor) = setup_test(&env);
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let backend_signer = Address::generate(&env);
+    client.initialize(&admin, &backend_signer, &backend_public_key(&env));
 
-    let spin_id = BytesN::from_array(&env, &[12u8; 32]);
-    let spin_hash = BytesN::from_array(&env, &[13u8; 32]);
-    let signature = BytesN::from_array(&env, &[6u8; 64]);
+    assert_eq!(
+        client.try_set_max_odds(&backend_signer, &50_000),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_prevent_double_betting(&backend_signer, &true),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_max_odds(&admin, &50_000);
+    assert_eq!(client.get_max_odds(), Some(50_000));
+}
 
-    client.execute_spin_with_ttl(&spin_id, &spin_hash, &signature, &executor, &Some(5));
-    assert!(client.is_spin_hash_used(&spin_hash));
+#[test]
+fn admin_cannot_authorize_spins() {
+    let env = Env::default();
+    let contract_id = env.register(BettingContract, ());
+    let client = BettingContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let backend_signer = Address::generate(&env);
+    let executor = Address::generate(&env);
+    client.initialize(&admin, &backend_signer, &backend_public_key(&env));
 
-    env.ledger().with_mut(|li| {
-        li.timestamp += 6;
-    });
+    let spin_id = BytesN::from_array(&env, &[1u8; 32]);
+    let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let signature = sign_spin(&env, &spin_id, &spin_hash);
+    let args = (
+        spin_id.clone(),
+        spin_hash.clone(),
+        signature.clone(),
+        executor.clone(),
+        0u64,
+    )
+        .into_val(&env);
+    let invoke = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "execute_spin",
+        args,
+        sub_invokes: &[],
+    };
+    env.mock_auths(&[
+        MockAuth {
+            address: &executor,
+            invoke: &invoke,
+        },
+        MockAuth {
+            address: &admin,
+            invoke: &invoke,
+        },
+    ]);
+
+    assert!(client
+        .try_execute_spin(&spin_id, &spin_hash, &signature, &executor, &0)
+        .is_err());
+    assert!(!client.is_spin_executed(&spin_id));
+}
+
+#[test]
+fn upgrade_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    assert!(client.cleanup_spin_hash(&spin_hash));
-    assert!(!client.is_spin_hash_used(&spin_hash));
+    let (client, _, stranger) = setup_test(&env);
+
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[1u8; 32])),
+        Err(Ok(ContractError::Unauthorized))
+    );
 }
 
 // ============================================
@@ -198,14 +3383,23 @@ fn execute_spin_without_auth_fails() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
 
     // Don't mock any auths - should fail due to executor.require_auth()
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+    );
 }
 
 // ============================================
@@ -221,7 +3415,11 @@ fn get_spin_execution_for_nonexistent_spin_fails() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let nonexistent_spin_id = BytesN::from_array(&env, &[99u8; 32]);
 
@@ -238,7 +3436,11 @@ fn is_spin_executed_returns_false_for_new_spin() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let new_spin_id = BytesN::from_array(&env, &[1u8; 32]);
 
@@ -254,7 +3456,11 @@ fn is_spin_hash_used_returns_false_for_new_hash() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let new_spin_hash = BytesN::from_array(&env, &[1u8; 32]);
 
@@ -270,7 +3476,11 @@ fn cleanup_spin_hash_returns_false_for_nonexistent_hash() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let nonexistent_hash = BytesN::from_array(&env, &[99u8; 32]);
 
@@ -287,14 +3497,25 @@ fn cleanup_spin_hash_returns_false_before_ttl_expires() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
+    let replay_hash = client.spin_replay_hash(&spin_id, &executor, &0);
 
     // Execute with 100 second TTL
-    client.execute_spin_with_ttl(&spin_id, &spin_hash, &signature, &executor, &Some(100));
+    client.execute_spin_with_ttl(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+        &Some(100),
+    );
 
     // Advance time but not past TTL
     env.ledger().with_mut(|li| {
@@ -302,8 +3523,8 @@ fn cleanup_spin_hash_returns_false_before_ttl_expires() {
     });
 
     // Cleanup should fail since TTL hasn't expired
-    assert!(!client.cleanup_spin_hash(&spin_hash));
-    assert!(client.is_spin_hash_used(&spin_hash));
+    assert!(!client.cleanup_spin_hash(&replay_hash));
+    assert!(client.is_spin_hash_used(&replay_hash));
 }
 
 #[test]
@@ -316,19 +3537,34 @@ fn different_spin_ids_same_hash_replay_fails() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let spin_id1 = BytesN::from_array(&env, &[1u8; 32]);
     let spin_id2 = BytesN::from_array(&env, &[2u8; 32]);
     let same_spin_hash = BytesN::from_array(&env, &[3u8; 32]);
-    let signature = BytesN::from_array(&env, &[4u8; 64]);
 
     // First execution succeeds
-    client.execute_spin(&spin_id1, &same_spin_hash, &signature, &executor);
+    client.execute_spin(
+        &spin_id1,
+        &same_spin_hash,
+        &sign_spin(&env, &spin_id1, &same_spin_hash),
+        &executor,
+        &0,
+    );
 
-    // Second execution with different spin_id but same hash should fail
-    let result = client.try_execute_spin(&spin_id2, &same_spin_hash, &signature, &executor);
-    assert_eq!(result, Err(Ok(ContractError::DuplicateOperation)));
+    // Second execution with different spin_id but same hash and nonce should fail
+    let result = client.try_execute_spin(
+        &spin_id2,
+        &same_spin_hash,
+        &sign_spin(&env, &spin_id2, &same_spin_hash),
+        &executor,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidNonce)));
 }
 
 #[test]
@@ -341,18 +3577,33 @@ fn same_spin_id_different_hash_fails() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let same_spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash1 = BytesN::from_array(&env, &[2u8; 32]);
     let spin_hash2 = BytesN::from_array(&env, &[3u8; 32]);
-    let signature = BytesN::from_array(&env, &[4u8; 64]);
 
     // First execution succeeds
-    client.execute_spin(&same_spin_id, &spin_hash1, &signature, &executor);
+    client.execute_spin(
+        &same_spin_id,
+        &spin_hash1,
+        &sign_spin(&env, &same_spin_id, &spin_hash1),
+        &executor,
+        &0,
+    );
 
     // Second execution with same spin_id but different hash should fail
-    let result = client.try_execute_spin(&same_spin_id, &spin_hash2, &signature, &executor);
+    let result = client.try_execute_spin(
+        &same_spin_id,
+        &spin_hash2,
+        &sign_spin(&env, &same_spin_id, &spin_hash2),
+        &executor,
+        &1,
+    );
     assert_eq!(result, Err(Ok(ContractError::SpinAlreadyExecuted)));
 }
 
@@ -366,18 +3617,27 @@ fn get_spin_execution_returns_correct_data() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
 
     // Set a specific timestamp
     env.ledger().with_mut(|li| {
         li.timestamp = 12345;
     });
 
-    client.execute_spin(&spin_id, &spin_hash, &signature, &executor);
+    client.execute_spin(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+    );
 
     let execution = client.get_spin_execution(&spin_id);
     assert_eq!(execution.spin_id, spin_id);
@@ -396,17 +3656,32 @@ fn multiple_executors_isolated() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let spin_id1 = BytesN::from_array(&env, &[1u8; 32]);
     let spin_id2 = BytesN::from_array(&env, &[2u8; 32]);
     let spin_hash1 = BytesN::from_array(&env, &[3u8; 32]);
     let spin_hash2 = BytesN::from_array(&env, &[4u8; 32]);
-    let signature = BytesN::from_array(&env, &[5u8; 64]);
 
     // Both executors can execute spins
-    client.execute_spin(&spin_id1, &spin_hash1, &signature, &executor1);
-    client.execute_spin(&spin_id2, &spin_hash2, &signature, &executor2);
+    client.execute_spin(
+        &spin_id1,
+        &spin_hash1,
+        &sign_spin(&env, &spin_id1, &spin_hash1),
+        &executor1,
+        &0,
+    );
+    client.execute_spin(
+        &spin_id2,
+        &spin_hash2,
+        &sign_spin(&env, &spin_id2, &spin_hash2),
+        &executor2,
+        &0,
+    );
 
     let execution1 = client.get_spin_execution(&spin_id1);
     let execution2 = client.get_spin_execution(&spin_id2);
@@ -425,15 +3700,25 @@ fn execute_spin_with_zero_ttl_immediate_cleanup() {
     let contract_id = env.register(BettingContract, ());
     let client = BettingContractClient::new(&env, &contract_id);
 
-    client.initialize(&backend_signer);
+    client.initialize(
+        &Address::generate(&env),
+        &backend_signer,
+        &backend_public_key(&env),
+    );
 
     let spin_id = BytesN::from_array(&env, &[1u8; 32]);
     let spin_hash = BytesN::from_array(&env, &[2u8; 32]);
-    let signature = BytesN::from_array(&env, &[3u8; 64]);
 
     // Execute with 0 TTL - operation is immediately expired per is_expired logic
     // (timestamp - executed_at >= 0 is always true when timestamp >= executed_at)
-    client.execute_spin_with_ttl(&spin_id, &spin_hash, &signature, &executor, &Some(0));
+    client.execute_spin_with_ttl(
+        &spin_id,
+        &spin_hash,
+        &sign_spin(&env, &spin_id, &spin_hash),
+        &executor,
+        &0,
+        &Some(0),
+    );
 
     // With TTL of 0, the operation is considered expired immediately
     // so it won't be stored (it gets cleaned up during ensure_not_replayed)
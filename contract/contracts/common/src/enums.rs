@@ -1,6 +1,6 @@
-use soroban_sdk::contracterror;
+use soroban_sdk::contracttype;
 
-#[contracterror]
+#[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum BetStatus {
@@ -10,7 +10,7 @@ pub enum BetStatus {
     Cancelled = 3,
 }
 
-#[contracterror]
+#[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum BetOutcome {
@@ -24,4 +24,21 @@ pub enum ContractError {
     BetAlreadyPlaced = 18,
     DuplicateOperation = 19,
     NoRewardsToClaim = 20,
+    AddressBlocked = 21,
+    InvalidUri = 22,
+    ArithmeticOverflow = 23,
+    ExposureLimitExceeded = 24,
+    ContractPaused = 25,
+    InvalidToken = 26,
+    BetBelowMinimum = 27,
+    BetAboveMaximum = 28,
+    InvalidOdds = 29,
+    MatchNotFound = 30,
+    MatchClosed = 31,
+    InvalidMatchWindow = 32,
+    MatchNotEnded = 33,
+    InvalidNonce = 34,
+    DailyLimitExceeded = 35,
+    InvalidFee = 36,
+    MatchAlreadyExists = 37,
 }
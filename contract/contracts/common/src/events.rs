@@ -78,6 +78,54 @@ pub struct NFTMintEvent {
     pub price: Option<i128>,
 }
 
+// Published when a card changes hands; kept off the mint topic so mint
+// indexers don't see transfers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NFTTransferEvent {
+    pub token_id: U256,
+    pub from: Address,
+    pub to: Address,
+    pub nft_contract: Address,
+    pub timestamp: u64,
+}
+
+// Published when a card's single-token approval is set, or cleared with
+// `approved: None`. Transfers clear the approval without a separate event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NFTApprovalEvent {
+    pub token_id: U256,
+    pub owner: Address,
+    pub approved: Option<Address>,
+    pub nft_contract: Address,
+    pub timestamp: u64,
+}
+
+// Published when a card is raised a level; kept off the mint topic so mint
+// indexers don't count upgrades as new cards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NFTUpgradeEvent {
+    pub token_id: U256,
+    pub owner: Address,
+    pub level: u32,
+    pub nft_contract: Address,
+    pub timestamp: u64,
+}
+
+// Published when an owner destroys a card; kept off the mint topic so mint
+// indexers don't see burns.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NFTBurnEvent {
+    pub token_id: U256,
+    pub owner: Address,
+    pub token_uri: String,
+    pub nft_contract: Address,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ReplayRejectedEvent {
@@ -104,6 +152,55 @@ pub struct BetPlacedEvent {
     pub amount: i128,
 }
 
+// Pull-model payout signal: published at settlement, topic-indexed by bettor,
+// so wallets can notify users that winnings are waiting to be claimed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinningsAvailableEvent {
+    pub bettor: Address,
+    pub match_id: BytesN<32>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+// Emitted when an open bet is withdrawn and its stake refunded. `amount` is
+// what the bettor got back, after any cancellation penalty.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetCancelledEvent {
+    pub bettor: Address,
+    pub match_id: BytesN<32>,
+    pub amount: i128,
+    pub reason: Symbol,
+    pub timestamp: u64,
+}
+
+// Published when an unclaimed settlement is corrected with `resettle_bet`;
+// `amount` is the bettor's new claimable payout for the match.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetResettledEvent {
+    pub bettor: Address,
+    pub match_id: BytesN<32>,
+    pub previous_amount: i128,
+    pub amount: i128,
+    pub settlement_type: Symbol,
+    pub timestamp: u64,
+}
+
+// Compact per-match record published once every bet on a match is settled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchSettledEvent {
+    pub match_id: BytesN<32>,
+    pub winning_outcome: Symbol,
+    pub total_bets: u32,
+    pub total_staked: i128,
+    pub total_payout: i128,
+    pub house_profit: i128,
+    pub timestamp: u64,
+}
+
 // ===== EVENT CONSTANTS =====
 
 pub const STAKE_EVENT: Symbol = symbol_short!("STAKE");
@@ -112,10 +209,22 @@ pub const BET_EVENT: Symbol = symbol_short!("BET");
 pub const SETTLEMENT_EVENT: Symbol = symbol_short!("SETTLE");
 pub const SPIN_REWARD_EVENT: Symbol = symbol_short!("SPIN_RWD");
 pub const NFT_MINT_EVENT: Symbol = symbol_short!("NFT_MINT");
+pub const NFT_TRANSFER_EVENT: Symbol = symbol_short!("NFT_XFER");
+pub const NFT_APPROVAL_EVENT: Symbol = symbol_short!("NFT_APPR");
+pub const NFT_UPGRADE_EVENT: Symbol = symbol_short!("NFT_UPGR");
+pub const NFT_BURN_EVENT: Symbol = symbol_short!("NFT_BURN");
+pub const WINNINGS_AVAILABLE_EVENT: Symbol = symbol_short!("WIN_AVAIL");
+pub const MATCH_SETTLED_EVENT: Symbol = symbol_short!("MATCH_SET");
+pub const BET_CANCELLED_EVENT: Symbol = symbol_short!("BET_CNCL");
+pub const BET_RESETTLED_EVENT: Symbol = symbol_short!("BET_RSTL");
+pub const BET_PLACED_EVENT: Symbol = symbol_short!("BET_PLCD");
+pub const SPIN_EXECUTED_EVENT: Symbol = symbol_short!("SPIN_EXEC");
 
 // ===== EVENT HELPERS =====
+// Each helper stamps the event with the current ledger timestamp.
 
 pub fn create_stake_event(
+    env: &Env,
     user: Address,
     amount: i128,
     token_address: Address,
@@ -127,12 +236,13 @@ pub fn create_stake_event(
         amount,
         token_address,
         staking_contract,
-        timestamp: 0, // Will be set by contract
+        timestamp: env.ledger().timestamp(),
         stake_id,
     }
 }
 
 pub fn create_unstake_event(
+    env: &Env,
     user: Address,
     amount: i128,
     token_address: Address,
@@ -145,7 +255,7 @@ pub fn create_unstake_event(
         amount,
         token_address,
         staking_contract,
-        timestamp: 0, // Will be set by contract
+        timestamp: env.ledger().timestamp(),
         stake_id,
         rewards,
     }
@@ -165,7 +275,7 @@ pub fn create_bet_event(
         amount,
         bet_id,
         betting_contract,
-        timestamp: 0, // Will be set by contract
+        timestamp: env.ledger().timestamp(),
         bet_type,
         odds,
         metadata: Map::new(env),
@@ -186,13 +296,37 @@ pub fn create_settlement_event(
         winner,
         payout,
         betting_contract,
-        timestamp: 0, // Will be set by contract
+        timestamp: env.ledger().timestamp(),
         settlement_type,
         final_odds,
         metadata: Map::new(env),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn create_spin_reward_event(
+    env: &Env,
+    user: Address,
+    reward_amount: i128,
+    token_address: Address,
+    game_contract: Address,
+    spin_id: U256,
+    reward_type: Symbol,
+    multiplier: u32,
+) -> SpinRewardEvent {
+    SpinRewardEvent {
+        user,
+        reward_amount,
+        token_address,
+        game_contract,
+        timestamp: env.ledger().timestamp(),
+        spin_id,
+        reward_type,
+        multiplier,
+        metadata: Map::new(env),
+    }
+}
+
 pub fn create_nft_mint_event(
     env: &Env,
     token_id: U256,
@@ -207,9 +341,45 @@ pub fn create_nft_mint_event(
         to,
         token_uri,
         nft_contract,
-        timestamp: 0, // Will be set by contract
+        timestamp: env.ledger().timestamp(),
         mint_type,
         metadata: Map::new(env),
         price,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    #[test]
+    fn helpers_stamp_the_ledger_timestamp() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_700_000_000);
+        let user = Address::generate(&env);
+        let contract = Address::generate(&env);
+        let id = U256::from_u32(&env, 7);
+
+        let stake = create_stake_event(
+            &env,
+            user.clone(),
+            100,
+            contract.clone(),
+            contract.clone(),
+            id.clone(),
+        );
+        assert_eq!(stake.timestamp, 1_700_000_000);
+
+        let bet = create_bet_event(
+            &env,
+            user,
+            100,
+            id,
+            contract,
+            Symbol::new(&env, "win"),
+            20_000,
+        );
+        assert_eq!(bet.timestamp, env.ledger().timestamp());
+    }
+}
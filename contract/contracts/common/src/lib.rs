@@ -5,6 +5,9 @@ pub mod errors;
 pub mod events;
 pub mod getters;
 pub mod idempotency;
+pub mod pause;
+pub mod rewards;
+pub mod ttl;
 pub mod view_functions;
 
 pub use enums::*;
@@ -12,3 +15,5 @@ pub use errors::*;
 pub use events::*;
 pub use getters::*;
 pub use idempotency::*;
+pub use pause::*;
+pub use ttl::*;
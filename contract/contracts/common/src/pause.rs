@@ -0,0 +1,27 @@
+use crate::ContractError;
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Entry point a contract exposes to act as a global pauser for others.
+/// It must report only its own local flag so pausers can point at each other.
+pub const IS_PAUSED_FN: &str = "is_paused";
+
+/// Reject the call if the contract is paused locally or its global pauser
+/// reports itself paused.
+pub fn ensure_not_paused(
+    env: &Env,
+    locally_paused: bool,
+    global_pauser: Option<Address>,
+) -> Result<(), ContractError> {
+    if locally_paused {
+        return Err(ContractError::ContractPaused);
+    }
+
+    if let Some(pauser) = global_pauser {
+        let paused: bool =
+            env.invoke_contract(&pauser, &Symbol::new(env, IS_PAUSED_FN), Vec::new(env));
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+    }
+    Ok(())
+}
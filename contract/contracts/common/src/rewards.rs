@@ -0,0 +1,41 @@
+use crate::errors::ContractError;
+
+/// Reward accrued at `rate` units per second between `staked_at` and `now`.
+///
+/// A `now` at or before `staked_at` accrues nothing. A result that doesn't fit
+/// in an `i128` is reported as `ArithmeticOverflow` rather than wrapping.
+pub fn accrued(rate: i128, staked_at: u64, now: u64) -> Result<i128, ContractError> {
+    let elapsed = now.saturating_sub(staked_at);
+    rate.checked_mul(elapsed as i128)
+        .ok_or(ContractError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zero_duration_accrues_nothing() {
+        assert_eq!(accrued(100, 1_000, 1_000), Ok(0));
+        assert_eq!(accrued(100, 1_000, 500), Ok(0));
+    }
+
+    #[test]
+    fn accrues_linearly_with_time() {
+        assert_eq!(accrued(5, 1_000, 1_060), Ok(300));
+        assert_eq!(accrued(0, 0, 1_000), Ok(0));
+    }
+
+    #[test]
+    fn reports_overflow() {
+        assert_eq!(
+            accrued(i128::MAX, 0, 2),
+            Err(ContractError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            accrued(i128::MAX / 2, 0, u64::MAX),
+            Err(ContractError::ArithmeticOverflow)
+        );
+        assert_eq!(accrued(i128::MAX, 0, 1), Ok(i128::MAX));
+    }
+}
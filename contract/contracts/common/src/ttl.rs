@@ -0,0 +1,24 @@
+use soroban_sdk::{Env, IntoVal, Val};
+
+/// Ledgers closed per day at the ~5 second close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Entries whose TTL has fallen below this many ledgers are extended on write.
+pub const TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+
+/// TTL, in ledgers, that entries are extended to.
+pub const TTL_EXTEND_TO: u32 = 90 * DAY_IN_LEDGERS;
+
+/// Keep a persistent entry from being archived while it is still in use.
+pub fn extend_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Keep the contract instance, its code and its instance storage alive.
+pub fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+}
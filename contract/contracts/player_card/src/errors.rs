@@ -1,6 +1,7 @@
 use soroban_sdk::contracterror;
 
 #[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
     AlreadyInitialized = 1,
@@ -10,4 +11,22 @@ pub enum Error {
     TokenNotFound = 5,
     BurnDisabled = 6,
     InvalidRecipient = 7,
+    InvalidRoyalty = 8,
+    TransferLimitReached = 9,
+    MetadataTooLarge = 10,
+    NotApproved = 11,
+    MaxLevelReached = 12,
+    TokenLocked = 13,
+    MaxSupplyReached = 14,
+    InvalidPrice = 15,
+    GateTokenUsed = 16,
+    SaleClosed = 17,
+    InvalidCollection = 18,
+    ContractPaused = 19,
+    AddressBlocked = 20,
+    InvalidUri = 21,
+    Overflow = 22,
+    DuplicateOperation = 23,
+    OperatorNotAllowed = 24,
+    GenesisClosed = 25,
 }
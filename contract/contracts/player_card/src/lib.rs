@@ -1,6 +1,9 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec, U256};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token::TokenClient, Address, BytesN, Env, Map, String,
+    Symbol, SymbolStr, TryFromVal, Vec, U256,
+};
 
 mod errors;
 mod events;
@@ -13,19 +16,263 @@ pub use storage::*;
 pub use token::*;
 
 use common::{
-    cleanup_operation, create_nft_mint_event, ensure_not_replayed, is_operation_executed,
-    ContractError, NFTMintEvent, NFT_MINT_EVENT,
+    cleanup_operation, create_nft_mint_event, ensure_not_paused, ensure_not_replayed,
+    extend_instance, is_operation_executed, NFTBurnEvent, NFTMintEvent, NFTTransferEvent,
+    NFTUpgradeEvent, NFT_BURN_EVENT, NFT_MINT_EVENT, NFT_TRANSFER_EVENT, NFT_UPGRADE_EVENT,
 };
 
+/// Royalties are expressed in basis points of the sale price (10000 == 100%).
+pub const MAX_ROYALTY_BPS: u32 = 10_000;
+
+/// Default cap on the number of metadata entries attached to a mint.
+pub const DEFAULT_MAX_METADATA_KEYS: u32 = 16;
+
+/// Default cap on token URI length in bytes.
+pub const DEFAULT_MAX_URI_LEN: u32 = 256;
+
+/// Release of the contract code, bumped whenever a new wasm is shipped.
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Default cap on how far a card can be upgraded.
+pub const DEFAULT_MAX_CARD_LEVEL: u32 = 10;
+
+/// Maximum number of provenance records returned per page.
+pub const MAX_PROVENANCE_PAGE: u32 = 50;
+
+/// Maximum number of token ids returned per `tokens_of_owner_paged` call.
+pub const MAX_OWNER_TOKENS_PAGE: u32 = 100;
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    if !storage::has_admin(env) {
+        return Err(Error::NotInitialized);
+    }
+    admin.require_auth();
+    if *admin != storage::get_admin(env) {
+        return Err(Error::NotAdmin);
+    }
+    Ok(())
+}
+
+fn symbol_to_string(env: &Env, symbol: &Symbol) -> String {
+    let symbol_str = SymbolStr::try_from_val(env, &symbol.to_symbol_val()).unwrap();
+    String::from_str(env, symbol_str.as_ref())
+}
+
+fn u32_to_string(env: &Env, value: u32) -> String {
+    let mut digits = [0u8; 10];
+    let mut start = digits.len();
+    let mut remaining = value;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+    String::from_str(env, core::str::from_utf8(&digits[start..]).unwrap())
+}
+
+/// Whether `count` more cards fit under the collection's supply cap, if any.
+/// Admin mints during the genesis window may exceed `max_supply` up to the
+/// genesis cap; every other mint is held to `max_supply`.
+fn has_supply_for(env: &Env, count: u64, admin_mint: bool) -> bool {
+    let new_supply = storage::get_total_supply(env).saturating_add(count);
+    if admin_mint {
+        if let Some((genesis_until, genesis_cap)) = storage::get_genesis_window(env) {
+            if env.ledger().timestamp() < genesis_until && new_supply <= genesis_cap {
+                return true;
+            }
+        }
+    }
+    match storage::get_max_supply(env) {
+        Some(max_supply) => new_supply <= max_supply,
+        None => true,
+    }
+}
+
+/// An empty allowlist accepts any URI; otherwise the URI must start with
+/// one of the configured prefixes.
+fn is_uri_allowed(env: &Env, token_uri: &String) -> bool {
+    let prefixes = storage::get_uri_prefixes(env);
+    if prefixes.is_empty() {
+        return true;
+    }
+
+    let uri = token_uri.to_bytes();
+    prefixes.iter().any(|prefix| {
+        let prefix = prefix.to_bytes();
+        prefix.len() <= uri.len() && uri.slice(0..prefix.len()) == prefix
+    })
+}
+
+/// Admin mints are authorized by the admin and made idempotent by operation hash.
+fn authorize_admin_mint(
+    env: &Env,
+    operation_hash: BytesN<32>,
+    ttl_seconds: Option<u64>,
+) -> Result<(), Error> {
+    if !storage::has_admin(env) {
+        return Err(Error::NotInitialized);
+    }
+    storage::get_admin(env).require_auth();
+    ensure_not_replayed(
+        env,
+        Symbol::new(env, "nft_mint"),
+        operation_hash,
+        ttl_seconds,
+    )
+    .map_err(|_| Error::DuplicateOperation)
+}
+
+fn ensure_active(env: &Env) -> Result<(), Error> {
+    ensure_not_paused(
+        env,
+        storage::is_paused(env),
+        storage::get_global_pauser(env),
+    )
+    .map_err(|_| Error::ContractPaused)
+}
+
+fn mint_token(
+    env: &Env,
+    to: &Address,
+    token_uri: &String,
+    metadata: Map<Symbol, String>,
+    price: Option<i128>,
+    admin_mint: bool,
+) -> Result<u64, Error> {
+    ensure_active(env)?;
+    if storage::is_blocked(env, to) {
+        return Err(Error::AddressBlocked);
+    }
+    if !is_uri_allowed(env, token_uri) {
+        return Err(Error::InvalidUri);
+    }
+    let (max_metadata_keys, max_uri_len) = storage::get_metadata_limits(env);
+    if metadata.len() > max_metadata_keys || token_uri.len() > max_uri_len {
+        return Err(Error::MetadataTooLarge);
+    }
+    if !has_supply_for(env, 1, admin_mint) {
+        return Err(Error::MaxSupplyReached);
+    }
+
+    let token_id = storage::get_next_token_id(env);
+    storage::increment_next_token_id(env)?;
+
+    storage::set_owner(env, token_id, to);
+    storage::set_token_uri(env, token_id, token_uri);
+    if !metadata.is_empty() {
+        storage::set_token_metadata(env, token_id, &metadata);
+    }
+    storage::append_provenance(
+        env,
+        token_id,
+        &TransferRecord {
+            from: None,
+            to: Some(to.clone()),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    let mut event = create_nft_mint_event(
+        env,
+        U256::from_u128(env, token_id as u128),
+        to.clone(),
+        token_uri.clone(),
+        env.current_contract_address(),
+        Symbol::new(env, "PLAYER_CARD"),
+        price,
+    );
+    event.metadata = metadata;
+
+    env.events().publish((NFT_MINT_EVENT,), event);
+
+    Ok(token_id)
+}
+
+/// Collection-wide configuration, bundled so marketplaces can read it in one call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionConfig {
+    pub admin: Address,
+    pub total_supply: u64,
+    pub royalty_receiver: Address,
+    pub royalty_bps: u32,
+    pub gate_collection: Option<Address>,
+    pub allowed_uri_prefixes: Vec<String>,
+    pub max_metadata_keys: u32,
+    pub max_uri_len: u32,
+    pub max_supply: Option<u64>,
+    pub paused: bool,
+    pub global_pauser: Option<Address>,
+}
+
+/// One link in a token's ownership chain. `from` is `None` for the mint and
+/// `to` is `None` for a burn.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferRecord {
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub timestamp: u64,
+}
+
+/// Move a card between owners once the caller has been authorized.
+fn move_token(env: &Env, from: Address, to: Address, token_id: u64) -> Result<(), Error> {
+    ensure_active(env)?;
+    if storage::is_blocked(env, &from) || storage::is_blocked(env, &to) {
+        return Err(Error::AddressBlocked);
+    }
+
+    let current_owner = storage::try_get_owner(env, token_id).ok_or(Error::TokenNotFound)?;
+    if current_owner != from {
+        return Err(Error::NotTokenOwner);
+    }
+    if storage::is_token_locked(env, token_id) {
+        return Err(Error::TokenLocked);
+    }
+
+    let max_transfers = storage::get_max_transfers(env, token_id);
+    let transfer_count = storage::get_transfer_count(env, token_id);
+    if max_transfers > 0 && transfer_count >= max_transfers {
+        return Err(Error::TransferLimitReached);
+    }
+    storage::set_transfer_count(env, token_id, transfer_count + 1);
+
+    storage::set_owner(env, token_id, &to);
+    PlayerCardToken::clear_approval(env, token_id);
+    storage::append_provenance(
+        env,
+        token_id,
+        &TransferRecord {
+            from: Some(from.clone()),
+            to: Some(to.clone()),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    let event = NFTTransferEvent {
+        token_id: U256::from_u128(env, token_id as u128),
+        from,
+        to,
+        nft_contract: env.current_contract_address(),
+        timestamp: env.ledger().timestamp(),
+    };
+
+    env.events().publish((NFT_TRANSFER_EVENT,), event);
+    Ok(())
+}
+
 #[contract]
 pub struct PlayerCardContract;
 
 #[contractimpl]
 impl PlayerCardContract {
     /// Initialize the contract with the given admin
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         if storage::has_admin(&env) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
         storage::set_admin(&env, &admin);
@@ -43,6 +290,28 @@ impl PlayerCardContract {
         };
 
         env.events().publish((NFT_MINT_EVENT,), event);
+        Ok(())
+    }
+
+    /// Release of the deployed contract code.
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract code in place, keeping all cards and settings.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    /// Extend the TTL of the contract instance, which holds the code and the
+    /// collection-wide settings. Per-card and per-holder entries are
+    /// persistent and have their TTL extended whenever they are written.
+    pub fn bump_ttl(env: Env, admin: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        extend_instance(&env);
+        Ok(())
     }
 
     /// Mint a new player card NFT to the specified recipient.
@@ -53,41 +322,190 @@ impl PlayerCardContract {
         to: Address,
         token_uri: String,
         ttl_seconds: Option<u64>,
-    ) -> Result<u64, ContractError> {
-        let admin = storage::get_admin(&env);
-        admin.require_auth();
-        ensure_not_replayed(
-            &env,
-            Symbol::new(&env, "nft_mint"),
-            operation_hash,
-            ttl_seconds,
-        )?;
+    ) -> Result<u64, Error> {
+        authorize_admin_mint(&env, operation_hash, ttl_seconds)?;
+        mint_token(&env, &to, &token_uri, Map::new(&env), None, true)
+    }
 
-        let token_id = storage::get_next_token_id(&env);
-        storage::increment_next_token_id(&env);
+    /// Mint one card per recipient in a single call, e.g. to seed a new season.
+    /// Token ids are assigned consecutively and returned in recipient order.
+    pub fn mint_batch(
+        env: Env,
+        recipients: Vec<Address>,
+        token_uris: Vec<String>,
+    ) -> Result<Vec<u64>, Error> {
+        if !storage::has_admin(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_admin(&env).require_auth();
 
-        storage::set_owner(&env, token_id, &to);
-        storage::set_token_uri(&env, token_id, &token_uri);
+        if recipients.len() != token_uris.len() {
+            return Err(Error::InvalidRecipient);
+        }
+        if !has_supply_for(&env, recipients.len() as u64, true) {
+            return Err(Error::MaxSupplyReached);
+        }
 
-        let event = create_nft_mint_event(
-            &env,
-            U256::from_u32(&env, token_id as u32),
-            to.clone(),
-            token_uri.clone(),
-            env.current_contract_address(),
-            Symbol::short("PLAYER_CARD"),
-            None,
+        let mut token_ids = Vec::new(&env);
+        for (to, token_uri) in recipients.iter().zip(token_uris.iter()) {
+            token_ids.push_back(mint_token(
+                &env,
+                &to,
+                &token_uri,
+                Map::new(&env),
+                None,
+                true,
+            )?);
+        }
+        Ok(token_ids)
+    }
+
+    /// Mint a utility card that can be transferred at most `max_transfers` times.
+    /// A `max_transfers` of 0 means unlimited, same as `mint`.
+    pub fn mint_limited(
+        env: Env,
+        operation_hash: BytesN<32>,
+        to: Address,
+        token_uri: String,
+        max_transfers: u32,
+        ttl_seconds: Option<u64>,
+    ) -> Result<u64, Error> {
+        authorize_admin_mint(&env, operation_hash, ttl_seconds)?;
+
+        let token_id = mint_token(&env, &to, &token_uri, Map::new(&env), None, true)?;
+        if max_transfers > 0 {
+            storage::set_max_transfers(&env, token_id, max_transfers);
+        }
+
+        Ok(token_id)
+    }
+
+    /// Mint a player card as the next edition of `series`.
+    /// Edition numbers start at 1 and count up per series.
+    pub fn mint_series(
+        env: Env,
+        operation_hash: BytesN<32>,
+        to: Address,
+        token_uri: String,
+        series: Symbol,
+        ttl_seconds: Option<u64>,
+    ) -> Result<u64, Error> {
+        authorize_admin_mint(&env, operation_hash, ttl_seconds)?;
+
+        let edition_number = storage::get_series_supply(&env, &series) + 1;
+
+        let mut metadata = Map::new(&env);
+        metadata.set(Symbol::new(&env, "series"), symbol_to_string(&env, &series));
+        metadata.set(
+            Symbol::new(&env, "edition"),
+            u32_to_string(&env, edition_number),
         );
 
-        let mut event_with_timestamp = event;
-        event_with_timestamp.timestamp = env.ledger().timestamp();
+        let token_id = mint_token(&env, &to, &token_uri, metadata, None, true)?;
 
-        env.events()
-            .publish((NFT_MINT_EVENT,), event_with_timestamp);
+        storage::set_token_series(&env, token_id, &series, edition_number);
+        storage::set_series_supply(&env, &series, edition_number);
 
         Ok(token_id)
     }
 
+    /// Get the series and edition number a token was minted in, if any
+    pub fn series_of(env: Env, token_id: u64) -> Option<(Symbol, u32)> {
+        storage::get_token_series(&env, token_id)
+    }
+
+    /// Get the number of cards minted in a series
+    pub fn series_supply(env: Env, series: Symbol) -> u32 {
+        storage::get_series_supply(&env, &series)
+    }
+
+    /// Set the player card collection whose holders may use `gated_mint`. A
+    /// collection can't gate itself, or every mint would unlock another.
+    pub fn set_gate_collection(env: Env, admin: Address, collection: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if collection == env.current_contract_address() {
+            return Err(Error::InvalidCollection);
+        }
+        storage::set_gate_collection(&env, &collection);
+        Ok(())
+    }
+
+    /// Get the player card collection that gates `gated_mint`, if configured
+    pub fn gate_collection(env: Env) -> Option<Address> {
+        storage::get_gate_collection(&env)
+    }
+
+    /// Mint a premium card to `buyer`, who must own `qualifying_token_id` in the
+    /// gate collection. Each qualifying token can unlock a single gated mint.
+    pub fn gated_mint(
+        env: Env,
+        buyer: Address,
+        token_uri: String,
+        qualifying_token_id: u64,
+    ) -> Result<u64, Error> {
+        buyer.require_auth();
+
+        let collection = storage::get_gate_collection(&env).ok_or(Error::NotInitialized)?;
+        if storage::is_gate_token_used(&env, qualifying_token_id) {
+            return Err(Error::GateTokenUsed);
+        }
+
+        // A missing token traps in the gate collection; treat that as not owned.
+        let gate = PlayerCardContractClient::new(&env, &collection);
+        match gate.try_owner_of(&qualifying_token_id) {
+            Ok(Ok(owner)) if owner == buyer => {}
+            _ => return Err(Error::NotTokenOwner),
+        }
+
+        storage::set_gate_token_used(&env, qualifying_token_id);
+        mint_token(&env, &buyer, &token_uri, Map::new(&env), None, false)
+    }
+
+    /// Sell a card to `to` at the admin-set price, paid to the admin before
+    /// minting. `token_address` and `price` must match the configured sale so
+    /// the buyer never pays more, or in another asset, than they agreed to.
+    /// The price is recorded in the mint event.
+    pub fn mint_with_price(
+        env: Env,
+        to: Address,
+        token_uri: String,
+        token_address: Address,
+        price: i128,
+    ) -> Result<u64, Error> {
+        to.require_auth();
+        if !storage::has_admin(&env) {
+            return Err(Error::NotInitialized);
+        }
+        let (sale_token, sale_price) = storage::get_mint_price(&env).ok_or(Error::SaleClosed)?;
+        if token_address != sale_token || price != sale_price {
+            return Err(Error::InvalidPrice);
+        }
+
+        TokenClient::new(&env, &token_address).transfer(&to, storage::get_admin(&env), &price);
+        mint_token(&env, &to, &token_uri, Map::new(&env), Some(price), false)
+    }
+
+    /// Open paid minting at `price` of `token_address`, or close it with `None`.
+    pub fn set_mint_price(
+        env: Env,
+        admin: Address,
+        price: Option<(Address, i128)>,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if let Some((_, amount)) = &price {
+            if *amount <= 0 {
+                return Err(Error::InvalidPrice);
+            }
+        }
+        storage::set_mint_price(&env, price);
+        Ok(())
+    }
+
+    /// Get the payment token and price for paid mints, or `None` if closed
+    pub fn mint_price(env: Env) -> Option<(Address, i128)> {
+        storage::get_mint_price(&env)
+    }
+
     pub fn is_mint_operation_executed(env: Env, operation_hash: BytesN<32>) -> bool {
         is_operation_executed(&env, Symbol::new(&env, "nft_mint"), operation_hash)
     }
@@ -97,43 +515,275 @@ impl PlayerCardContract {
     }
 
     /// Transfer ownership of a token from one address to another
-    pub fn transfer(env: Env, from: Address, to: Address, token_id: u64) {
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u64) -> Result<(), Error> {
         from.require_auth();
+        move_token(&env, from, to, token_id)
+    }
+
+    /// Transfer a card on the owner's behalf. `spender` must be the owner, the
+    /// token's approved address, or an operator approved for all of the owner's cards.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+    ) -> Result<(), Error> {
+        spender.require_auth();
 
-        let current_owner = storage::get_owner(&env, token_id);
-        if current_owner != from {
-            panic!("not token owner");
+        if !PlayerCardToken::is_approved_or_owner(env.clone(), spender, token_id)? {
+            return Err(Error::NotApproved);
         }
+        move_token(&env, from, to, token_id)
+    }
 
-        storage::set_owner(&env, token_id, &to);
+    /// Approve `approved` to transfer a single card; requires the owner's auth
+    pub fn approve(env: Env, approved: Address, token_id: u64) -> Result<(), Error> {
+        PlayerCardToken::approve(env, approved, token_id)
+    }
 
-        let event = NFTMintEvent {
-            token_id: U256::from_u32(&env, token_id as u32),
-            to: to.clone(),
-            token_uri: storage::get_token_uri(&env, token_id),
+    /// Clear a card's approval; requires the owner's auth
+    pub fn revoke_approval(env: Env, token_id: u64) -> Result<(), Error> {
+        PlayerCardToken::revoke_approval(env, token_id)
+    }
+
+    /// Get the address approved to transfer a card, if any
+    pub fn get_approved(env: Env, token_id: u64) -> Option<Address> {
+        PlayerCardToken::get_approved(env, token_id)
+    }
+
+    /// Freeze a card in place, e.g. while it's staked or entered in a
+    /// tournament. Locked cards can't be transferred or burned. Only the admin
+    /// can lock or unlock, so an owner can't pull a card out of play
+    /// mid-tournament.
+    pub fn lock_token(env: Env, admin: Address, token_id: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if storage::try_get_owner(&env, token_id).is_none() {
+            return Err(Error::TokenNotFound);
+        }
+        storage::set_token_locked(&env, token_id, true);
+        Ok(())
+    }
+
+    /// Make a locked card transferable again
+    pub fn unlock_token(env: Env, admin: Address, token_id: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_token_locked(&env, token_id, false);
+        Ok(())
+    }
+
+    /// Check if a card is locked against transfers
+    pub fn is_locked(env: Env, token_id: u64) -> bool {
+        storage::is_token_locked(&env, token_id)
+    }
+
+    /// Raise a card one level, up to the configured maximum. Returns the new level.
+    pub fn upgrade_card(env: Env, admin: Address, token_id: u64) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        let owner = storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)?;
+
+        let level = storage::get_card_level(&env, token_id);
+        if level >= storage::get_max_card_level(&env) {
+            return Err(Error::MaxLevelReached);
+        }
+        let level = level + 1;
+        storage::set_card_level(&env, token_id, level);
+
+        let event = NFTUpgradeEvent {
+            token_id: U256::from_u128(&env, token_id as u128),
+            owner,
+            level,
             nft_contract: env.current_contract_address(),
             timestamp: env.ledger().timestamp(),
-            mint_type: Symbol::short("TRANSFER"),
-            metadata: soroban_sdk::Map::new(&env),
-            price: None,
         };
+        env.events().publish((NFT_UPGRADE_EVENT,), event);
 
-        env.events().publish((NFT_MINT_EVENT,), event);
+        Ok(level)
+    }
+
+    /// Get a card's level; cards that were never upgraded are level 1
+    pub fn get_card_level(env: Env, token_id: u64) -> u32 {
+        storage::get_card_level(&env, token_id)
+    }
+
+    /// Set the highest level `upgrade_card` can take a card to
+    pub fn set_max_card_level(env: Env, admin: Address, max_level: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_max_card_level(&env, max_level);
+        Ok(())
+    }
+
+    /// Get the highest level a card can reach
+    pub fn max_card_level(env: Env) -> u32 {
+        storage::get_max_card_level(&env)
+    }
+
+    /// Replace a card's attributes (e.g. position, rating, team)
+    pub fn set_token_metadata(
+        env: Env,
+        admin: Address,
+        token_id: u64,
+        metadata: Map<Symbol, String>,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if storage::try_get_owner(&env, token_id).is_none() {
+            return Err(Error::TokenNotFound);
+        }
+        let (max_metadata_keys, _) = storage::get_metadata_limits(&env);
+        if metadata.len() > max_metadata_keys {
+            return Err(Error::MetadataTooLarge);
+        }
+
+        storage::set_token_metadata(&env, token_id, &metadata);
+        Ok(())
+    }
+
+    /// Get a card's attributes. Cards minted without metadata return an empty map.
+    pub fn token_metadata(env: Env, token_id: u64) -> Map<Symbol, String> {
+        storage::get_token_metadata(&env, token_id)
+    }
+
+    /// Get a page of a token's ownership history, oldest first, starting at the
+    /// mint. At most `MAX_PROVENANCE_PAGE` records are returned per call.
+    pub fn provenance(env: Env, token_id: u64, start: u32, limit: u32) -> Vec<TransferRecord> {
+        let len = storage::get_provenance_len(&env, token_id);
+        let end = start
+            .saturating_add(limit.min(MAX_PROVENANCE_PAGE))
+            .min(len);
+
+        let mut records = Vec::new(&env);
+        for index in start..end {
+            if let Some(record) = storage::get_provenance_record(&env, token_id, index) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    /// Permanently destroy a card. Only its current owner can burn it, and
+    /// not while it is locked.
+    pub fn burn(env: Env, from: Address, token_id: u64) -> Result<(), Error> {
+        from.require_auth();
+        ensure_active(&env)?;
+
+        let owner = storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)?;
+        if owner != from {
+            return Err(Error::NotTokenOwner);
+        }
+        if storage::is_token_locked(&env, token_id) {
+            return Err(Error::TokenLocked);
+        }
+
+        let token_uri = storage::get_token_uri(&env, token_id);
+        storage::remove_token(&env, token_id);
+        PlayerCardToken::clear_approval(&env, token_id);
+        storage::set_burned_count(&env, storage::get_burned_count(&env) + 1);
+        storage::append_provenance(
+            &env,
+            token_id,
+            &TransferRecord {
+                from: Some(from.clone()),
+                to: None,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        let event = NFTBurnEvent {
+            token_id: U256::from_u128(&env, token_id as u128),
+            owner: from,
+            token_uri,
+            nft_contract: env.current_contract_address(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((NFT_BURN_EVENT,), event);
+
+        Ok(())
+    }
+
+    /// Let `operator` manage every card `owner` holds, now and in the future.
+    /// While operators are restricted, only allowlisted operators can be
+    /// approved; revoking is always allowed.
+    pub fn set_approval_for_all(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        if approved
+            && storage::is_operator_restricted(&env)
+            && !storage::is_operator_allowed(&env, &operator)
+        {
+            return Err(Error::OperatorNotAllowed);
+        }
+        storage::set_approval_for_all(&env, &owner, &operator, approved);
+        Ok(())
+    }
+
+    /// Limit `set_approval_for_all` to allowlisted operators, or reopen it to
+    /// any operator. Existing approvals are unaffected.
+    pub fn set_operator_restriction(
+        env: Env,
+        admin: Address,
+        restricted: bool,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_operator_restricted(&env, restricted);
+        Ok(())
+    }
+
+    /// Check whether approvals are limited to allowlisted operators
+    pub fn is_operator_restricted(env: Env) -> bool {
+        storage::is_operator_restricted(&env)
+    }
+
+    /// Add `operator`, e.g. a marketplace, to the operator allowlist
+    pub fn allow_operator(env: Env, admin: Address, operator: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_operator_allowed(&env, &operator, true);
+        Ok(())
+    }
+
+    /// Remove `operator` from the operator allowlist
+    pub fn disallow_operator(env: Env, admin: Address, operator: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_operator_allowed(&env, &operator, false);
+        Ok(())
+    }
+
+    /// Check if `operator` is on the operator allowlist
+    pub fn is_operator_allowed(env: Env, operator: Address) -> bool {
+        storage::is_operator_allowed(&env, &operator)
+    }
+
+    /// Check if `operator` may manage all of `owner`'s cards
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        storage::is_approved_for_all(&env, &owner, &operator)
+    }
+
+    /// Get how many more times a token can be transferred, or `None` if unlimited
+    pub fn remaining_transfers(env: Env, token_id: u64) -> Option<u32> {
+        let max_transfers = storage::get_max_transfers(&env, token_id);
+        if max_transfers == 0 {
+            return None;
+        }
+        Some(max_transfers.saturating_sub(storage::get_transfer_count(&env, token_id)))
     }
 
     /// Get the owner of a specific token
-    pub fn owner_of(env: Env, token_id: u64) -> Address {
-        storage::get_owner(&env, token_id)
+    pub fn owner_of(env: Env, token_id: u64) -> Result<Address, Error> {
+        storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)
     }
 
     /// Get the metadata URI for a specific token
-    pub fn token_uri(env: Env, token_id: u64) -> String {
-        storage::get_token_uri(&env, token_id)
+    pub fn token_uri(env: Env, token_id: u64) -> Result<String, Error> {
+        storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)?;
+        Ok(storage::get_token_uri(&env, token_id))
     }
 
     /// Get total number of tokens minted
     pub fn total_supply(env: Env) -> u64 {
-        storage::get_next_token_id(&env) - 1
+        storage::get_total_supply(&env)
     }
 
     /// Get all tokens owned by a specific address
@@ -141,6 +791,232 @@ impl PlayerCardContract {
         storage::get_tokens_of_owner(&env, owner)
     }
 
+    /// Page through an owner's tokens, `limit` ids starting at `start`.
+    /// At most `MAX_OWNER_TOKENS_PAGE` ids are returned per call.
+    pub fn tokens_of_owner_paged(env: Env, owner: Address, start: u32, limit: u32) -> Vec<u64> {
+        let tokens = storage::get_tokens_of_owner(&env, owner);
+        if start >= tokens.len() {
+            return Vec::new(&env);
+        }
+        let end = start
+            .saturating_add(limit.min(MAX_OWNER_TOKENS_PAGE))
+            .min(tokens.len());
+        tokens.slice(start..end)
+    }
+
+    /// Number of tokens held by `owner`
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        storage::get_tokens_of_owner(&env, owner).len()
+    }
+
+    /// Nominate a new admin. The handoff completes only once they call `accept_admin`.
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &current_admin)?;
+        storage::set_pending_admin(&env, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin nomination
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        new_admin.require_auth();
+        if storage::get_pending_admin(&env) != Some(new_admin.clone()) {
+            return Err(Error::NotAdmin);
+        }
+
+        storage::set_admin(&env, &new_admin);
+        storage::remove_pending_admin(&env);
+        Ok(())
+    }
+
+    /// Get the nominated admin awaiting acceptance, if any
+    pub fn pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Set the collection-wide royalty receiver and rate in basis points
+    pub fn set_default_royalty(
+        env: Env,
+        admin: Address,
+        receiver: Address,
+        bps: u32,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if bps > MAX_ROYALTY_BPS {
+            return Err(Error::InvalidRoyalty);
+        }
+
+        storage::set_default_royalty(&env, &receiver, bps);
+        Ok(())
+    }
+
+    /// Get the collection-wide royalty receiver and rate in basis points.
+    /// Defaults to the admin at 0 bps when no royalty has been configured.
+    pub fn default_royalty(env: Env) -> (Address, u32) {
+        storage::get_default_royalty(&env).unwrap_or_else(|| (storage::get_admin(&env), 0))
+    }
+
+    /// Get the full collection configuration
+    pub fn collection_config(env: Env) -> Result<CollectionConfig, Error> {
+        if !storage::has_admin(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        let (royalty_receiver, royalty_bps) = Self::default_royalty(env.clone());
+        let (max_metadata_keys, max_uri_len) = storage::get_metadata_limits(&env);
+        Ok(CollectionConfig {
+            admin: storage::get_admin(&env),
+            total_supply: Self::total_supply(env.clone()),
+            royalty_receiver,
+            royalty_bps,
+            gate_collection: storage::get_gate_collection(&env),
+            allowed_uri_prefixes: storage::get_uri_prefixes(&env),
+            max_metadata_keys,
+            max_uri_len,
+            max_supply: storage::get_max_supply(&env),
+            paused: storage::is_paused(&env),
+            global_pauser: storage::get_global_pauser(&env),
+        })
+    }
+
+    /// Limit the edition to `max_supply` cards in circulation, or lift the cap
+    /// with `None`. The cap can't be set below the current supply.
+    pub fn set_max_supply(env: Env, admin: Address, max_supply: Option<u64>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if let Some(max_supply) = max_supply {
+            if max_supply < storage::get_total_supply(&env) {
+                return Err(Error::MaxSupplyReached);
+            }
+        }
+        storage::set_max_supply(&env, max_supply);
+        Ok(())
+    }
+
+    /// Get the supply cap, or `None` if minting is unlimited
+    pub fn max_supply(env: Env) -> Option<u64> {
+        storage::get_max_supply(&env)
+    }
+
+    /// Let admin mints exceed `max_supply` up to `genesis_cap` cards until
+    /// `genesis_until`, to pre-mint a genesis set. The window can only be
+    /// opened before the first card is minted and must end in the future.
+    pub fn set_genesis_window(
+        env: Env,
+        admin: Address,
+        genesis_until: u64,
+        genesis_cap: u64,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if storage::get_next_token_id(&env) > 1 || genesis_until <= env.ledger().timestamp() {
+            return Err(Error::GenesisClosed);
+        }
+        storage::set_genesis_window(&env, genesis_until, genesis_cap);
+        Ok(())
+    }
+
+    /// Get the `(genesis_until, genesis_cap)` window, if one was opened
+    pub fn genesis_window(env: Env) -> Option<(u64, u64)> {
+        storage::get_genesis_window(&env)
+    }
+
+    /// Cap the number of metadata entries and the token URI length accepted at mint
+    pub fn set_metadata_limits(
+        env: Env,
+        admin: Address,
+        max_metadata_keys: u32,
+        max_uri_len: u32,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_metadata_limits(&env, max_metadata_keys, max_uri_len);
+        Ok(())
+    }
+
+    /// Get `(max_metadata_keys, max_uri_len)`
+    pub fn metadata_limits(env: Env) -> (u32, u32) {
+        storage::get_metadata_limits(&env)
+    }
+
+    /// Restrict mintable token URIs to those starting with an approved prefix
+    /// (e.g. a trusted metadata host). With no prefixes configured any URI is accepted.
+    pub fn add_allowed_uri_prefix(env: Env, admin: Address, prefix: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+
+        let mut prefixes = storage::get_uri_prefixes(&env);
+        if !prefixes.contains(&prefix) {
+            prefixes.push_back(prefix);
+            storage::set_uri_prefixes(&env, &prefixes);
+        }
+        Ok(())
+    }
+
+    /// Remove a previously approved URI prefix
+    pub fn remove_allowed_uri_prefix(
+        env: Env,
+        admin: Address,
+        prefix: String,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+
+        let mut prefixes = storage::get_uri_prefixes(&env);
+        if let Some(index) = prefixes.first_index_of(&prefix) {
+            prefixes.remove(index);
+            storage::set_uri_prefixes(&env, &prefixes);
+        }
+        Ok(())
+    }
+
+    /// Get the approved token URI prefixes
+    pub fn allowed_uri_prefixes(env: Env) -> Vec<String> {
+        storage::get_uri_prefixes(&env)
+    }
+
+    /// Pause or resume minting and transfers on this contract
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_paused(&env, paused);
+        Ok(())
+    }
+
+    /// Check the local pause flag. Other contracts consult this when they use
+    /// this one as their global pauser, so it deliberately ignores our own pauser.
+    pub fn is_paused(env: Env) -> bool {
+        storage::is_paused(&env)
+    }
+
+    /// Halt this contract whenever `pauser` reports itself paused
+    pub fn set_global_pauser(env: Env, admin: Address, pauser: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_global_pauser(&env, &pauser);
+        Ok(())
+    }
+
+    /// Get the contract consulted for a global pause, if any
+    pub fn global_pauser(env: Env) -> Option<Address> {
+        storage::get_global_pauser(&env)
+    }
+
+    /// Block an address from receiving or sending cards (compliance)
+    pub fn block_address(env: Env, admin: Address, addr: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_blocked(&env, &addr, true);
+        Ok(())
+    }
+
+    /// Lift a block previously placed with `block_address`
+    pub fn unblock_address(env: Env, admin: Address, addr: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_blocked(&env, &addr, false);
+        Ok(())
+    }
+
+    /// Check if an address is blocked
+    pub fn is_blocked(env: Env, addr: Address) -> bool {
+        storage::is_blocked(&env, &addr)
+    }
+
     // ===== BACKEND VERIFICATION GETTERS =====
 
     /// Get user's NFT balance (number of tokens owned)
@@ -159,7 +1035,7 @@ impl PlayerCardContract {
     /// Get NFT contract statistics
     /// Read-only getter for backend verification
     pub fn get_nft_contract_stats(env: Env) -> (u64, Address) {
-        let total_supply = storage::get_next_token_id(&env) - 1;
+        let total_supply = storage::get_total_supply(&env);
         let admin = storage::get_admin(&env);
         (total_supply, admin)
     }
@@ -167,8 +1043,7 @@ impl PlayerCardContract {
     /// Check if a token exists
     /// Read-only getter for backend verification
     pub fn token_exists(env: Env, token_id: u64) -> bool {
-        let key = (String::from_str(&env, "TOKEN_OWNER"), token_id);
-        env.storage().instance().has(&key)
+        storage::try_get_owner(&env, token_id).is_some()
     }
 
     /// Get token metadata in a structured format
@@ -205,3 +1080,6 @@ impl PlayerCardContract {
         (balance, token_metadata)
     }
 }
+
+#[cfg(test)]
+mod test;
@@ -1,10 +1,75 @@
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::{
+    Error, TransferRecord, DEFAULT_MAX_CARD_LEVEL, DEFAULT_MAX_METADATA_KEYS, DEFAULT_MAX_URI_LEN,
+};
+use common::{extend_instance, extend_persistent};
+use soroban_sdk::{Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec};
 
 const ADMIN: &str = "ADMIN";
+const PENDING_ADMIN: &str = "PENDING_ADMIN";
 const NEXT_TOKEN_ID: &str = "NEXT_TOKEN_ID";
 const TOKEN_OWNER: &str = "TOKEN_OWNER";
 const TOKEN_URI: &str = "TOKEN_URI";
+const TOKEN_METADATA: &str = "TOKEN_METADATA";
 const OWNER_TOKENS: &str = "OWNER_TOKENS";
+const DEFAULT_ROYALTY: &str = "DEFAULT_ROYALTY";
+const BLOCKED: &str = "BLOCKED";
+const TOKEN_SERIES: &str = "TOKEN_SERIES";
+const SERIES_SUPPLY: &str = "SERIES_SUPPLY";
+const URI_PREFIXES: &str = "URI_PREFIXES";
+const GATE_COLLECTION: &str = "GATE_COLLECTION";
+const GATE_TOKEN_USED: &str = "GATE_TOKEN_USED";
+const MAX_TRANSFERS: &str = "MAX_TRANSFERS";
+const TRANSFER_COUNT: &str = "TRANSFER_COUNT";
+const BURNED_COUNT: &str = "BURNED_COUNT";
+const OPERATOR_APPROVAL: &str = "OPERATOR_APPROVAL";
+const PAUSED: &str = "PAUSED";
+const GLOBAL_PAUSER: &str = "GLOBAL_PAUSER";
+const METADATA_LIMITS: &str = "METADATA_LIMITS";
+const PROVENANCE: &str = "PROVENANCE";
+const PROVENANCE_LEN: &str = "PROVENANCE_LEN";
+const CARD_LEVEL: &str = "CARD_LEVEL";
+const MAX_CARD_LEVEL: &str = "MAX_CARD_LEVEL";
+const TOKEN_LOCKED: &str = "TOKEN_LOCKED";
+const MAX_SUPPLY: &str = "MAX_SUPPLY";
+const MINT_PRICE: &str = "MINT_PRICE";
+const GENESIS_WINDOW: &str = "GENESIS_WINDOW";
+const OPERATORS_RESTRICTED: &str = "OPERATORS_RESTRICTED";
+const ALLOWED_OPERATOR: &str = "ALLOWED_OPERATOR";
+
+// Per-token and per-account entries live in persistent storage with their own
+// TTL, so the instance entry stays small however many cards and holders there
+// are. Entries written before the move are still read from instance storage
+// and migrate on their next write.
+
+fn get_token_entry<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    env.storage()
+        .persistent()
+        .get(key)
+        .or_else(|| env.storage().instance().get(key))
+}
+
+fn has_token_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> bool {
+    env.storage().persistent().has(key) || env.storage().instance().has(key)
+}
+
+fn set_token_entry<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().instance().remove(key);
+    env.storage().persistent().set(key, value);
+    extend_persistent(env, key);
+}
+
+fn remove_token_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    env.storage().instance().remove(key);
+    env.storage().persistent().remove(key);
+}
 
 pub fn has_admin(env: &Env) -> bool {
     env.storage().instance().has(&String::from_str(env, ADMIN))
@@ -23,6 +88,24 @@ pub fn set_admin(env: &Env, admin: &Address) {
         .set(&String::from_str(env, ADMIN), admin);
 }
 
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, PENDING_ADMIN))
+}
+
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, PENDING_ADMIN), admin);
+}
+
+pub fn remove_pending_admin(env: &Env) {
+    env.storage()
+        .instance()
+        .remove(&String::from_str(env, PENDING_ADMIN));
+}
+
 pub fn get_next_token_id(env: &Env) -> u64 {
     env.storage()
         .instance()
@@ -36,56 +119,144 @@ pub fn set_next_token_id(env: &Env, token_id: u64) {
         .set(&String::from_str(env, NEXT_TOKEN_ID), &token_id);
 }
 
-pub fn increment_next_token_id(env: &Env) {
-    let current_id = get_next_token_id(env);
-    set_next_token_id(env, current_id + 1);
+pub fn increment_next_token_id(env: &Env) -> Result<(), Error> {
+    let next_id = get_next_token_id(env)
+        .checked_add(1)
+        .ok_or(Error::Overflow)?;
+    set_next_token_id(env, next_id);
+    Ok(())
 }
 
-pub fn get_owner(env: &Env, token_id: u64) -> Address {
-    let key = (String::from_str(env, TOKEN_OWNER), token_id);
+/// Token ids start at 1, so the supply is one less than the next id,
+/// less any tokens that have since been burned.
+pub fn get_total_supply(env: &Env) -> u64 {
+    get_next_token_id(env)
+        .saturating_sub(1)
+        .saturating_sub(get_burned_count(env))
+}
+
+/// `None` means the collection can grow without limit.
+pub fn get_max_supply(env: &Env) -> Option<u64> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, MAX_SUPPLY))
+}
+
+pub fn set_max_supply(env: &Env, max_supply: Option<u64>) {
+    let key = String::from_str(env, MAX_SUPPLY);
+    match max_supply {
+        Some(max_supply) => env.storage().instance().set(&key, &max_supply),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+/// Returns `(genesis_until, genesis_cap)` if a genesis window was opened.
+pub fn get_genesis_window(env: &Env) -> Option<(u64, u64)> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, GENESIS_WINDOW))
+}
+
+pub fn set_genesis_window(env: &Env, genesis_until: u64, genesis_cap: u64) {
+    env.storage().instance().set(
+        &String::from_str(env, GENESIS_WINDOW),
+        &(genesis_until, genesis_cap),
+    );
+}
+
+/// Payment token and price for `mint_with_price`; `None` while sales are closed.
+pub fn get_mint_price(env: &Env) -> Option<(Address, i128)> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, MINT_PRICE))
+}
+
+pub fn set_mint_price(env: &Env, price: Option<(Address, i128)>) {
+    let key = String::from_str(env, MINT_PRICE);
+    match price {
+        Some(price) => env.storage().instance().set(&key, &price),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+pub fn get_burned_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, BURNED_COUNT))
+        .unwrap_or(0)
+}
+
+pub fn set_burned_count(env: &Env, count: u64) {
     env.storage()
         .instance()
-        .get(&key)
-        .unwrap_or_else(|| panic!("token not found"))
+        .set(&String::from_str(env, BURNED_COUNT), &count);
+}
+
+pub fn get_owner(env: &Env, token_id: u64) -> Address {
+    try_get_owner(env, token_id).unwrap_or_else(|| panic!("token not found"))
+}
+
+pub fn try_get_owner(env: &Env, token_id: u64) -> Option<Address> {
+    let key = (String::from_str(env, TOKEN_OWNER), token_id);
+    get_token_entry(env, &key)
+}
+
+/// Drop a token's owner, URI, metadata, level, lock and owner-index entries.
+pub fn remove_token(env: &Env, token_id: u64) {
+    let owner_key = (String::from_str(env, TOKEN_OWNER), token_id);
+    if let Some(owner) = get_token_entry::<_, Address>(env, &owner_key) {
+        remove_token_from_owner(env, &owner, token_id);
+    }
+    remove_token_entry(env, &owner_key);
+    remove_token_entry(env, &(String::from_str(env, TOKEN_URI), token_id));
+    remove_token_entry(env, &(String::from_str(env, TOKEN_METADATA), token_id));
+    remove_token_entry(env, &(String::from_str(env, CARD_LEVEL), token_id));
+    remove_token_entry(env, &(String::from_str(env, TOKEN_LOCKED), token_id));
 }
 
 pub fn set_owner(env: &Env, token_id: u64, owner: &Address) {
     let key = (String::from_str(env, TOKEN_OWNER), token_id);
 
-    if let Some(old_owner) = env.storage().instance().get::<_, Address>(&key) {
+    if let Some(old_owner) = get_token_entry::<_, Address>(env, &key) {
         remove_token_from_owner(env, &old_owner, token_id);
     }
 
-    env.storage().instance().set(&key, owner);
+    set_token_entry(env, &key, owner);
     add_token_to_owner(env, owner, token_id);
+    // The instance still holds the admin and counters every call reads
+    extend_instance(env);
 }
 
 pub fn get_token_uri(env: &Env, token_id: u64) -> String {
     let key = (String::from_str(env, TOKEN_URI), token_id);
-    env.storage()
-        .instance()
-        .get(&key)
-        .unwrap_or_else(|| panic!("token not found"))
+    get_token_entry(env, &key).unwrap_or_else(|| panic!("token not found"))
 }
 
 pub fn set_token_uri(env: &Env, token_id: u64, token_uri: &String) {
     let key = (String::from_str(env, TOKEN_URI), token_id);
-    env.storage().instance().set(&key, token_uri);
+    set_token_entry(env, &key, token_uri);
+}
+
+pub fn get_token_metadata(env: &Env, token_id: u64) -> Map<Symbol, String> {
+    let key = (String::from_str(env, TOKEN_METADATA), token_id);
+    get_token_entry(env, &key).unwrap_or_else(|| Map::new(env))
+}
+
+pub fn set_token_metadata(env: &Env, token_id: u64, metadata: &Map<Symbol, String>) {
+    let key = (String::from_str(env, TOKEN_METADATA), token_id);
+    set_token_entry(env, &key, metadata);
 }
 
 pub fn get_tokens_of_owner(env: &Env, owner: Address) -> Vec<u64> {
     let key = (String::from_str(env, OWNER_TOKENS), owner);
-    env.storage()
-        .instance()
-        .get(&key)
-        .unwrap_or_else(|| Vec::new(env))
+    get_token_entry(env, &key).unwrap_or_else(|| Vec::new(env))
 }
 
 pub fn add_token_to_owner(env: &Env, owner: &Address, token_id: u64) {
     let key = (String::from_str(env, OWNER_TOKENS), owner);
     let mut tokens = get_tokens_of_owner(env, owner.clone());
     tokens.push_back(token_id);
-    env.storage().instance().set(&key, &tokens);
+    set_token_entry(env, &key, &tokens);
 }
 
 pub fn remove_token_from_owner(env: &Env, owner: &Address, token_id: u64) {
@@ -95,6 +266,268 @@ pub fn remove_token_from_owner(env: &Env, owner: &Address, token_id: u64) {
     let index = tokens.iter().position(|id| id == token_id);
     if let Some(index) = index {
         tokens.remove(index as u32);
-        env.storage().instance().set(&key, &tokens);
+        set_token_entry(env, &key, &tokens);
     }
 }
+
+pub fn get_default_royalty(env: &Env) -> Option<(Address, u32)> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, DEFAULT_ROYALTY))
+}
+
+pub fn set_default_royalty(env: &Env, receiver: &Address, bps: u32) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, DEFAULT_ROYALTY), &(receiver, bps));
+}
+
+pub fn is_blocked(env: &Env, addr: &Address) -> bool {
+    let key = (String::from_str(env, BLOCKED), addr.clone());
+    has_token_entry(env, &key)
+}
+
+pub fn set_blocked(env: &Env, addr: &Address, blocked: bool) {
+    let key = (String::from_str(env, BLOCKED), addr.clone());
+    if blocked {
+        set_token_entry(env, &key, &true);
+    } else {
+        remove_token_entry(env, &key);
+    }
+}
+
+pub fn get_token_series(env: &Env, token_id: u64) -> Option<(Symbol, u32)> {
+    let key = (String::from_str(env, TOKEN_SERIES), token_id);
+    get_token_entry(env, &key)
+}
+
+pub fn set_token_series(env: &Env, token_id: u64, series: &Symbol, edition_number: u32) {
+    let key = (String::from_str(env, TOKEN_SERIES), token_id);
+    set_token_entry(env, &key, &(series.clone(), edition_number));
+}
+
+pub fn get_series_supply(env: &Env, series: &Symbol) -> u32 {
+    let key = (String::from_str(env, SERIES_SUPPLY), series.clone());
+    get_token_entry(env, &key).unwrap_or(0)
+}
+
+pub fn set_series_supply(env: &Env, series: &Symbol, supply: u32) {
+    let key = (String::from_str(env, SERIES_SUPPLY), series.clone());
+    set_token_entry(env, &key, &supply);
+}
+
+pub fn get_uri_prefixes(env: &Env) -> Vec<String> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, URI_PREFIXES))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_uri_prefixes(env: &Env, prefixes: &Vec<String>) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, URI_PREFIXES), prefixes);
+}
+
+pub fn get_gate_collection(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, GATE_COLLECTION))
+}
+
+pub fn set_gate_collection(env: &Env, collection: &Address) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, GATE_COLLECTION), collection);
+}
+
+pub fn is_gate_token_used(env: &Env, token_id: u64) -> bool {
+    let key = (String::from_str(env, GATE_TOKEN_USED), token_id);
+    has_token_entry(env, &key)
+}
+
+pub fn set_gate_token_used(env: &Env, token_id: u64) {
+    let key = (String::from_str(env, GATE_TOKEN_USED), token_id);
+    set_token_entry(env, &key, &true);
+}
+
+pub fn get_approved(env: &Env, token_id: u64) -> Option<Address> {
+    get_token_entry(env, &("approval", token_id))
+}
+
+pub fn set_approved(env: &Env, token_id: u64, approved: &Address) {
+    set_token_entry(env, &("approval", token_id), approved);
+}
+
+pub fn remove_approved(env: &Env, token_id: u64) {
+    remove_token_entry(env, &("approval", token_id));
+}
+
+pub fn get_max_transfers(env: &Env, token_id: u64) -> u32 {
+    let key = (String::from_str(env, MAX_TRANSFERS), token_id);
+    get_token_entry(env, &key).unwrap_or(0)
+}
+
+pub fn set_max_transfers(env: &Env, token_id: u64, max_transfers: u32) {
+    let key = (String::from_str(env, MAX_TRANSFERS), token_id);
+    set_token_entry(env, &key, &max_transfers);
+}
+
+pub fn get_transfer_count(env: &Env, token_id: u64) -> u32 {
+    let key = (String::from_str(env, TRANSFER_COUNT), token_id);
+    get_token_entry(env, &key).unwrap_or(0)
+}
+
+pub fn set_transfer_count(env: &Env, token_id: u64, count: u32) {
+    let key = (String::from_str(env, TRANSFER_COUNT), token_id);
+    set_token_entry(env, &key, &count);
+}
+
+/// Cards start at level 1 until upgraded.
+pub fn get_card_level(env: &Env, token_id: u64) -> u32 {
+    let key = (String::from_str(env, CARD_LEVEL), token_id);
+    get_token_entry(env, &key).unwrap_or(1)
+}
+
+pub fn set_card_level(env: &Env, token_id: u64, level: u32) {
+    let key = (String::from_str(env, CARD_LEVEL), token_id);
+    set_token_entry(env, &key, &level);
+}
+
+pub fn get_max_card_level(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, MAX_CARD_LEVEL))
+        .unwrap_or(DEFAULT_MAX_CARD_LEVEL)
+}
+
+pub fn set_max_card_level(env: &Env, max_level: u32) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, MAX_CARD_LEVEL), &max_level);
+}
+
+pub fn is_token_locked(env: &Env, token_id: u64) -> bool {
+    let key = (String::from_str(env, TOKEN_LOCKED), token_id);
+    has_token_entry(env, &key)
+}
+
+pub fn set_token_locked(env: &Env, token_id: u64, locked: bool) {
+    let key = (String::from_str(env, TOKEN_LOCKED), token_id);
+    if locked {
+        set_token_entry(env, &key, &true);
+    } else {
+        remove_token_entry(env, &key);
+    }
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, PAUSED))
+        .unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, PAUSED), &paused);
+}
+
+pub fn get_global_pauser(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, GLOBAL_PAUSER))
+}
+
+pub fn set_global_pauser(env: &Env, pauser: &Address) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, GLOBAL_PAUSER), pauser);
+}
+
+/// Returns `(max_metadata_keys, max_uri_len)`.
+pub fn get_metadata_limits(env: &Env) -> (u32, u32) {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, METADATA_LIMITS))
+        .unwrap_or((DEFAULT_MAX_METADATA_KEYS, DEFAULT_MAX_URI_LEN))
+}
+
+pub fn set_metadata_limits(env: &Env, max_metadata_keys: u32, max_uri_len: u32) {
+    env.storage().instance().set(
+        &String::from_str(env, METADATA_LIMITS),
+        &(max_metadata_keys, max_uri_len),
+    );
+}
+
+pub fn is_approved_for_all(env: &Env, owner: &Address, operator: &Address) -> bool {
+    let key = (
+        String::from_str(env, OPERATOR_APPROVAL),
+        owner.clone(),
+        operator.clone(),
+    );
+    has_token_entry(env, &key)
+}
+
+pub fn set_approval_for_all(env: &Env, owner: &Address, operator: &Address, approved: bool) {
+    let key = (
+        String::from_str(env, OPERATOR_APPROVAL),
+        owner.clone(),
+        operator.clone(),
+    );
+    if approved {
+        set_token_entry(env, &key, &true);
+    } else {
+        remove_token_entry(env, &key);
+    }
+}
+
+pub fn is_operator_restricted(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&String::from_str(env, OPERATORS_RESTRICTED))
+        .unwrap_or(false)
+}
+
+pub fn set_operator_restricted(env: &Env, restricted: bool) {
+    env.storage()
+        .instance()
+        .set(&String::from_str(env, OPERATORS_RESTRICTED), &restricted);
+}
+
+pub fn is_operator_allowed(env: &Env, operator: &Address) -> bool {
+    let key = (String::from_str(env, ALLOWED_OPERATOR), operator.clone());
+    has_token_entry(env, &key)
+}
+
+pub fn set_operator_allowed(env: &Env, operator: &Address, allowed: bool) {
+    let key = (String::from_str(env, ALLOWED_OPERATOR), operator.clone());
+    if allowed {
+        set_token_entry(env, &key, &true);
+    } else {
+        remove_token_entry(env, &key);
+    }
+}
+
+pub fn get_provenance_len(env: &Env, token_id: u64) -> u32 {
+    let key = (String::from_str(env, PROVENANCE_LEN), token_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn get_provenance_record(env: &Env, token_id: u64, index: u32) -> Option<TransferRecord> {
+    let key = (String::from_str(env, PROVENANCE), token_id, index);
+    env.storage().persistent().get(&key)
+}
+
+/// Provenance grows with every transfer, so each record lives in its own
+/// persistent entry rather than in instance storage.
+pub fn append_provenance(env: &Env, token_id: u64, record: &TransferRecord) {
+    let len = get_provenance_len(env, token_id);
+    let key = (String::from_str(env, PROVENANCE), token_id, len);
+    let len_key = (String::from_str(env, PROVENANCE_LEN), token_id);
+    env.storage().persistent().set(&key, record);
+    env.storage().persistent().set(&len_key, &(len + 1));
+    extend_persistent(env, &key);
+    extend_persistent(env, &len_key);
+}
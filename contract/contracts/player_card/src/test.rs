@@ -0,0 +1,1632 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Events, Ledger,
+    },
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
+};
+
+/// Events published by `contract`, in order.
+fn contract_events(env: &Env, contract: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+    let mut events = Vec::new(env);
+    for event in env.events().all().iter() {
+        if event.0 == *contract {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+fn setup_test(env: &Env) -> (PlayerCardContractClient<'_>, Address) {
+    let contract_id = env.register(PlayerCardContract, ());
+    let client = PlayerCardContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (client, admin)
+}
+
+fn mint_card(env: &Env, client: &PlayerCardContractClient, seed: u8, to: &Address) -> u64 {
+    client.mint(
+        &BytesN::from_array(env, &[seed; 32]),
+        to,
+        &String::from_str(env, "ipfs://card"),
+        &None,
+    )
+}
+
+#[test]
+fn default_royalty_falls_back_to_admin_at_zero() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+
+    assert_eq!(client.default_royalty(), (admin, 0));
+}
+
+#[test]
+fn default_royalty_returns_configured_values() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    client.set_default_royalty(&admin, &receiver, &500);
+
+    assert_eq!(client.default_royalty(), (receiver, 500));
+}
+
+#[test]
+fn set_default_royalty_rejects_invalid_rate_and_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_default_royalty(&admin, &receiver, &(MAX_ROYALTY_BPS + 1)),
+        Err(Ok(Error::InvalidRoyalty))
+    );
+    assert_eq!(
+        client.try_set_default_royalty(&receiver, &receiver, &100),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn blocked_address_cannot_receive_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let user = Address::generate(&env);
+
+    client.block_address(&admin, &user);
+    assert!(client.is_blocked(&user));
+
+    let result = client.try_mint(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &user,
+        &String::from_str(&env, "ipfs://card"),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(Error::AddressBlocked)));
+
+    client.unblock_address(&admin, &user);
+    assert_eq!(mint_card(&env, &client, 1, &user), 1);
+}
+
+#[test]
+fn replayed_mint_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+
+    assert_eq!(mint_card(&env, &client, 1, &user), 1);
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &user,
+            &String::from_str(&env, "ipfs://card"),
+            &None,
+        ),
+        Err(Ok(Error::DuplicateOperation))
+    );
+    assert_eq!(client.total_supply(), 1);
+}
+
+#[test]
+fn blocked_address_cannot_receive_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let blocked = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.block_address(&admin, &blocked);
+
+    assert!(client.try_transfer(&owner, &blocked, &token_id).is_err());
+    assert_eq!(client.owner_of(&token_id), owner);
+}
+
+#[test]
+fn blocked_owner_cannot_transfer_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.block_address(&admin, &owner);
+
+    assert!(client.try_transfer(&owner, &recipient, &token_id).is_err());
+    assert_eq!(client.owner_of(&token_id), owner);
+}
+
+#[test]
+fn series_and_edition_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://card");
+    let series_2024 = Symbol::new(&env, "S2024");
+    let series_2025 = Symbol::new(&env, "S2025");
+
+    let first = client.mint_series(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &user,
+        &uri,
+        &series_2024,
+        &None,
+    );
+    let second = client.mint_series(
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &user,
+        &uri,
+        &series_2024,
+        &None,
+    );
+    let other = client.mint_series(
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &user,
+        &uri,
+        &series_2025,
+        &None,
+    );
+    let plain = mint_card(&env, &client, 4, &user);
+
+    assert_eq!(client.series_of(&first), Some((series_2024.clone(), 1)));
+    assert_eq!(client.series_of(&second), Some((series_2024.clone(), 2)));
+    assert_eq!(client.series_of(&other), Some((series_2025.clone(), 1)));
+    assert_eq!(client.series_of(&plain), None);
+    assert_eq!(client.series_supply(&series_2024), 2);
+    assert_eq!(client.series_supply(&series_2025), 1);
+}
+
+#[test]
+fn mint_series_event_carries_series_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://card");
+
+    let token_id = client.mint_series(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &user,
+        &uri,
+        &Symbol::new(&env, "S2024"),
+        &None,
+    );
+
+    let mut metadata = Map::new(&env);
+    metadata.set(Symbol::new(&env, "series"), String::from_str(&env, "S2024"));
+    metadata.set(Symbol::new(&env, "edition"), String::from_str(&env, "1"));
+    let event = NFTMintEvent {
+        token_id: U256::from_u128(&env, token_id as u128),
+        to: user,
+        token_uri: uri,
+        nft_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+        mint_type: Symbol::new(&env, "PLAYER_CARD"),
+        metadata,
+        price: None,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (NFT_MINT_EVENT,).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn transfer_publishes_transfer_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.transfer(&owner, &recipient, &token_id);
+
+    let event = NFTTransferEvent {
+        token_id: U256::from_u128(&env, token_id as u128),
+        from: owner,
+        to: recipient,
+        nft_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (NFT_TRANSFER_EVENT,).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn paid_mint_charges_buyer_and_records_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://card");
+    let payment = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let payment_client = TokenClient::new(&env, &payment);
+    StellarAssetClient::new(&env, &payment).mint(&buyer, &1_000);
+    client.set_mint_price(&admin, &Some((payment.clone(), 300)));
+
+    let token_id = client.mint_with_price(&buyer, &uri, &payment, &300);
+
+    let event = NFTMintEvent {
+        token_id: U256::from_u128(&env, token_id as u128),
+        to: buyer.clone(),
+        token_uri: uri.clone(),
+        nft_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+        mint_type: Symbol::new(&env, "PLAYER_CARD"),
+        metadata: Map::new(&env),
+        price: Some(300),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (NFT_MINT_EVENT,).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.owner_of(&token_id), buyer);
+    assert_eq!(payment_client.balance(&buyer), 700);
+    assert_eq!(payment_client.balance(&admin), 300);
+}
+
+#[test]
+fn paid_mint_rejects_non_positive_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let payment = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    assert_eq!(
+        client.try_set_mint_price(&admin, &Some((payment, 0))),
+        Err(Ok(Error::InvalidPrice))
+    );
+    assert_eq!(client.mint_price(), None);
+}
+
+#[test]
+fn paid_mint_only_accepts_configured_token_and_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://card");
+    let payment = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let other = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &payment).mint(&buyer, &1_000);
+    StellarAssetClient::new(&env, &other).mint(&buyer, &1_000);
+
+    assert_eq!(
+        client.try_mint_with_price(&buyer, &uri, &payment, &300),
+        Err(Ok(Error::SaleClosed))
+    );
+
+    client.set_mint_price(&admin, &Some((payment.clone(), 300)));
+    assert_eq!(client.mint_price(), Some((payment.clone(), 300)));
+    assert_eq!(
+        client.try_mint_with_price(&buyer, &uri, &other, &300),
+        Err(Ok(Error::InvalidPrice))
+    );
+    assert_eq!(
+        client.try_mint_with_price(&buyer, &uri, &payment, &1),
+        Err(Ok(Error::InvalidPrice))
+    );
+    assert_eq!(client.balance_of(&buyer), 0);
+    assert_eq!(TokenClient::new(&env, &payment).balance(&buyer), 1_000);
+
+    client.set_mint_price(&admin, &None);
+    assert_eq!(
+        client.try_mint_with_price(&buyer, &uri, &payment, &300),
+        Err(Ok(Error::SaleClosed))
+    );
+}
+
+#[test]
+fn any_uri_accepted_without_prefixes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+
+    assert_eq!(client.allowed_uri_prefixes().len(), 0);
+    assert_eq!(mint_card(&env, &client, 1, &user), 1);
+}
+
+#[test]
+fn mint_enforces_allowed_uri_prefixes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let user = Address::generate(&env);
+
+    client.add_allowed_uri_prefix(&admin, &String::from_str(&env, "ipfs://"));
+    client.add_allowed_uri_prefix(&admin, &String::from_str(&env, "https://cards.renaissance"));
+
+    let accepted = client.mint(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &user,
+        &String::from_str(&env, "https://cards.renaissance/42.json"),
+        &None,
+    );
+    assert_eq!(client.owner_of(&accepted), user);
+    assert_eq!(mint_card(&env, &client, 2, &user), accepted + 1);
+
+    let rejected = client.try_mint(
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &user,
+        &String::from_str(&env, "https://phishing.example/42.json"),
+        &None,
+    );
+    assert_eq!(rejected, Err(Ok(Error::InvalidUri)));
+
+    // A URI shorter than every prefix is rejected too
+    let short = client.try_mint(
+        &BytesN::from_array(&env, &[4u8; 32]),
+        &user,
+        &String::from_str(&env, "ipfs"),
+        &None,
+    );
+    assert_eq!(short, Err(Ok(Error::InvalidUri)));
+
+    client.remove_allowed_uri_prefix(&admin, &String::from_str(&env, "https://cards.renaissance"));
+    assert_eq!(client.allowed_uri_prefixes().len(), 1);
+}
+
+#[test]
+fn gated_mint_requires_qualifying_card() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (base, _) = setup_test(&env);
+    let (premium, premium_admin) = setup_test(&env);
+    let holder = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://premium");
+
+    let qualifying = mint_card(&env, &base, 1, &holder);
+
+    // No gate configured yet
+    assert_eq!(
+        premium.try_gated_mint(&holder, &uri, &qualifying),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    assert_eq!(
+        premium.try_set_gate_collection(&premium_admin, &premium.address),
+        Err(Ok(Error::InvalidCollection))
+    );
+    premium.set_gate_collection(&premium_admin, &base.address);
+    assert_eq!(premium.gate_collection(), Some(base.address.clone()));
+
+    assert_eq!(
+        premium.try_gated_mint(&outsider, &uri, &qualifying),
+        Err(Ok(Error::NotTokenOwner))
+    );
+    assert_eq!(
+        premium.try_gated_mint(&holder, &uri, &999),
+        Err(Ok(Error::NotTokenOwner))
+    );
+
+    let token_id = premium.gated_mint(&holder, &uri, &qualifying);
+    assert_eq!(premium.owner_of(&token_id), holder);
+
+    // The qualifying card has been spent
+    assert_eq!(
+        premium.try_gated_mint(&holder, &uri, &qualifying),
+        Err(Ok(Error::GateTokenUsed))
+    );
+}
+
+#[test]
+fn limited_card_rejects_transfers_past_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    let token_id = client.mint_limited(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &first,
+        &String::from_str(&env, "ipfs://utility"),
+        &2,
+        &None,
+    );
+    assert_eq!(client.remaining_transfers(&token_id), Some(2));
+
+    client.transfer(&first, &second, &token_id);
+    client.transfer(&second, &third, &token_id);
+    assert_eq!(client.remaining_transfers(&token_id), Some(0));
+
+    assert_eq!(
+        client.try_transfer(&third, &first, &token_id),
+        Err(Ok(Error::TransferLimitReached))
+    );
+    assert_eq!(client.owner_of(&token_id), third);
+}
+
+#[test]
+fn unlimited_card_has_no_transfer_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &first);
+
+    assert_eq!(client.remaining_transfers(&token_id), None);
+    client.transfer(&first, &second, &token_id);
+    client.transfer(&second, &first, &token_id);
+    assert_eq!(client.remaining_transfers(&token_id), None);
+}
+
+#[test]
+fn collection_config_bundles_individual_settings() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let receiver = Address::generate(&env);
+    let gate = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let prefix = String::from_str(&env, "ipfs://");
+
+    client.set_default_royalty(&admin, &receiver, &250);
+    client.set_gate_collection(&admin, &gate);
+    client.add_allowed_uri_prefix(&admin, &prefix);
+    mint_card(&env, &client, 1, &Address::generate(&env));
+    client.set_global_pauser(&admin, &pauser);
+    client.set_paused(&admin, &true);
+
+    assert_eq!(
+        client.collection_config(),
+        CollectionConfig {
+            admin: admin.clone(),
+            total_supply: client.total_supply(),
+            royalty_receiver: receiver,
+            royalty_bps: 250,
+            gate_collection: client.gate_collection(),
+            allowed_uri_prefixes: client.allowed_uri_prefixes(),
+            max_metadata_keys: DEFAULT_MAX_METADATA_KEYS,
+            max_uri_len: DEFAULT_MAX_URI_LEN,
+            max_supply: None,
+            paused: true,
+            global_pauser: Some(pauser),
+        }
+    );
+    assert_eq!(client.collection_config().total_supply, 1);
+}
+
+#[test]
+fn collection_config_requires_initialization() {
+    let env = Env::default();
+    let contract_id = env.register(PlayerCardContract, ());
+    let client = PlayerCardContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_collection_config(),
+        Err(Ok(Error::NotInitialized))
+    );
+}
+
+#[test]
+fn total_supply_is_zero_before_any_mint() {
+    let env = Env::default();
+    let contract_id = env.register(PlayerCardContract, ());
+    let client = PlayerCardContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.total_supply(), 0);
+
+    env.as_contract(&contract_id, || storage::set_next_token_id(&env, 0));
+    assert_eq!(client.total_supply(), 0);
+}
+
+#[test]
+fn mint_rejects_token_id_overflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+
+    env.as_contract(&client.address, || {
+        storage::set_next_token_id(&env, u64::MAX - 1)
+    });
+    assert_eq!(mint_card(&env, &client, 1, &user), u64::MAX - 1);
+    assert_eq!(client.total_supply(), u64::MAX - 1);
+
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &user,
+            &String::from_str(&env, "ipfs://card"),
+            &None,
+        ),
+        Err(Ok(Error::Overflow))
+    );
+    assert_eq!(client.total_supply(), u64::MAX - 1);
+}
+
+#[test]
+fn tokens_of_owner_pages_through_holdings() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    assert_eq!(client.balance_of(&owner), 0);
+
+    let mut minted = Vec::new(&env);
+    for seed in 1..=5u8 {
+        minted.push_back(mint_card(&env, &client, seed, &owner));
+    }
+    assert_eq!(client.balance_of(&owner), 5);
+
+    assert_eq!(
+        client.tokens_of_owner_paged(&owner, &1, &2),
+        vec![&env, minted.get(1).unwrap(), minted.get(2).unwrap()]
+    );
+    // A page running past the end is truncated
+    assert_eq!(client.tokens_of_owner_paged(&owner, &4, &10).len(), 1);
+    assert_eq!(client.tokens_of_owner_paged(&owner, &5, &10).len(), 0);
+    assert_eq!(
+        client.tokens_of_owner_paged(&owner, &u32::MAX, &10).len(),
+        0
+    );
+    assert_eq!(client.tokens_of_owner_paged(&owner, &0, &u32::MAX), minted);
+}
+
+#[test]
+fn provenance_records_mint_and_transfers_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    env.ledger().set_timestamp(100);
+    let token_id = mint_card(&env, &client, 1, &first);
+    env.ledger().set_timestamp(200);
+    client.transfer(&first, &second, &token_id);
+    env.ledger().set_timestamp(300);
+    client.transfer(&second, &third, &token_id);
+
+    assert_eq!(
+        client.provenance(&token_id, &0, &10),
+        vec![
+            &env,
+            TransferRecord {
+                from: None,
+                to: Some(first.clone()),
+                timestamp: 100,
+            },
+            TransferRecord {
+                from: Some(first),
+                to: Some(second.clone()),
+                timestamp: 200,
+            },
+            TransferRecord {
+                from: Some(second.clone()),
+                to: Some(third.clone()),
+                timestamp: 300,
+            },
+        ]
+    );
+
+    // Paged reads
+    let page = client.provenance(&token_id, &2, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().from, Some(second));
+    assert!(client.provenance(&token_id, &3, &10).is_empty());
+}
+
+#[test]
+fn mint_enforces_uri_length_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let user = Address::generate(&env);
+    assert_eq!(
+        client.metadata_limits(),
+        (DEFAULT_MAX_METADATA_KEYS, DEFAULT_MAX_URI_LEN)
+    );
+
+    client.set_metadata_limits(&admin, &DEFAULT_MAX_METADATA_KEYS, &11);
+
+    // "ipfs://card" is exactly 11 bytes
+    mint_card(&env, &client, 1, &user);
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &user,
+            &String::from_str(&env, "ipfs://card2"),
+            &None,
+        ),
+        Err(Ok(Error::MetadataTooLarge))
+    );
+}
+
+#[test]
+fn mint_enforces_metadata_key_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://card");
+    let series = Symbol::new(&env, "S2024");
+
+    // Series mints carry two metadata entries
+    client.set_metadata_limits(&admin, &2, &DEFAULT_MAX_URI_LEN);
+    client.mint_series(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &user,
+        &uri,
+        &series,
+        &None,
+    );
+
+    client.set_metadata_limits(&admin, &1, &DEFAULT_MAX_URI_LEN);
+    assert_eq!(
+        client.try_mint_series(
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &user,
+            &uri,
+            &series,
+            &None,
+        ),
+        Err(Ok(Error::MetadataTooLarge))
+    );
+}
+
+#[test]
+fn only_admin_can_set_metadata_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_metadata_limits(&outsider, &1, &1),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn only_admin_can_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let outsider = Address::generate(&env);
+
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(
+        client.try_upgrade(&outsider, &BytesN::from_array(&env, &[1u8; 32])),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn card_ownership_outlives_default_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    env.as_contract(&client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), common::TTL_EXTEND_TO);
+        let persistent = env.storage().persistent();
+        let owner_key = (String::from_str(&env, "TOKEN_OWNER"), token_id);
+        let uri_key = (String::from_str(&env, "TOKEN_URI"), token_id);
+        let tokens_key = (String::from_str(&env, "OWNER_TOKENS"), owner.clone());
+        assert_eq!(persistent.get_ttl(&owner_key), common::TTL_EXTEND_TO);
+        assert_eq!(persistent.get_ttl(&uri_key), common::TTL_EXTEND_TO);
+        assert_eq!(persistent.get_ttl(&tokens_key), common::TTL_EXTEND_TO);
+        assert!(!env.storage().instance().has(&owner_key));
+    });
+
+    // Well past the default TTL but inside the extension
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 60 * common::DAY_IN_LEDGERS);
+    assert_eq!(client.owner_of(&token_id), owner);
+    assert_eq!(
+        client.token_uri(&token_id),
+        String::from_str(&env, "ipfs://card")
+    );
+    assert_eq!(client.tokens_of_owner(&owner), vec![&env, token_id]);
+    client.bump_ttl(&admin);
+    assert_eq!(client.try_bump_ttl(&owner), Err(Ok(Error::NotAdmin)));
+}
+
+#[test]
+fn approvals_and_holder_flags_outlive_default_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+    let series = Symbol::new(&env, "S2024");
+    client.mint_series(
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &owner,
+        &String::from_str(&env, "ipfs://card"),
+        &series,
+        &None,
+    );
+    client.approve(&operator, &token_id);
+    client.set_approval_for_all(&owner, &operator, &true);
+    client.block_address(&admin, &operator);
+
+    env.as_contract(&client.address, || {
+        let approval_key = ("approval", token_id);
+        let operator_key = (
+            String::from_str(&env, "OPERATOR_APPROVAL"),
+            owner.clone(),
+            operator.clone(),
+        );
+        let blocked_key = (String::from_str(&env, "BLOCKED"), operator.clone());
+        let series_key = (String::from_str(&env, "SERIES_SUPPLY"), series.clone());
+        let persistent = env.storage().persistent();
+        assert_eq!(persistent.get_ttl(&approval_key), common::TTL_EXTEND_TO);
+        assert_eq!(persistent.get_ttl(&operator_key), common::TTL_EXTEND_TO);
+        assert_eq!(persistent.get_ttl(&blocked_key), common::TTL_EXTEND_TO);
+        assert_eq!(persistent.get_ttl(&series_key), common::TTL_EXTEND_TO);
+        assert!(!env.storage().instance().has(&approval_key));
+    });
+
+    client.unblock_address(&admin, &operator);
+    assert!(!client.is_blocked(&operator));
+    assert_eq!(client.get_approved(&token_id), Some(operator));
+}
+
+#[test]
+fn cards_stored_in_instance_storage_migrate_on_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    // A card written by a release that kept ownership in instance storage
+    let owner_key = (String::from_str(&env, "TOKEN_OWNER"), 1u64);
+    let tokens_key = (String::from_str(&env, "OWNER_TOKENS"), owner.clone());
+    env.as_contract(&client.address, || {
+        let instance = env.storage().instance();
+        instance.set(&owner_key, &owner);
+        instance.set(&tokens_key, &vec![&env, 1u64]);
+        instance.set(&String::from_str(&env, "NEXT_TOKEN_ID"), &2u64);
+    });
+
+    assert_eq!(client.owner_of(&1), owner);
+    assert!(client.token_exists(&1));
+
+    client.transfer(&owner, &buyer, &1);
+    assert_eq!(client.owner_of(&1), buyer);
+    assert_eq!(client.tokens_of_owner(&owner).len(), 0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&owner_key));
+        assert!(env.storage().persistent().has(&owner_key));
+    });
+}
+
+#[test]
+fn paused_card_contract_rejects_mints_and_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &first);
+
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &first,
+            &String::from_str(&env, "ipfs://card"),
+            &None,
+        ),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_transfer(&first, &second, &token_id),
+        Err(Ok(Error::ContractPaused))
+    );
+
+    client.set_paused(&admin, &false);
+    client.transfer(&first, &second, &token_id);
+    assert_eq!(client.owner_of(&token_id), second);
+}
+
+#[test]
+fn owner_can_burn_card() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let burned = mint_card(&env, &client, 1, &owner);
+    let kept = mint_card(&env, &client, 2, &owner);
+    assert_eq!(client.total_supply(), 2);
+
+    client.burn(&owner, &burned);
+
+    let event = NFTBurnEvent {
+        token_id: U256::from_u128(&env, burned as u128),
+        owner: owner.clone(),
+        token_uri: String::from_str(&env, "ipfs://card"),
+        nft_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (NFT_BURN_EVENT,).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.total_supply(), 1);
+    assert!(!client.token_exists(&burned));
+    assert_eq!(client.tokens_of_owner(&owner), vec![&env, kept]);
+    assert_eq!(client.provenance(&burned, &1, &10).get(0).unwrap().to, None);
+    assert_eq!(
+        client.try_burn(&owner, &burned),
+        Err(Ok(Error::TokenNotFound))
+    );
+
+    // New mints keep their own ids
+    assert_eq!(mint_card(&env, &client, 3, &owner), 3);
+    assert_eq!(client.total_supply(), 2);
+}
+
+#[test]
+fn non_owner_cannot_burn_card() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    assert_eq!(
+        client.try_burn(&admin, &token_id),
+        Err(Ok(Error::NotTokenOwner))
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+    assert_eq!(client.total_supply(), 1);
+}
+
+#[test]
+fn locked_card_cannot_be_burned() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.lock_token(&admin, &token_id);
+    assert_eq!(
+        client.try_burn(&owner, &token_id),
+        Err(Ok(Error::TokenLocked))
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+    assert_eq!(client.total_supply(), 1);
+
+    client.unlock_token(&admin, &token_id);
+    client.burn(&owner, &token_id);
+    assert!(!client.token_exists(&token_id));
+}
+
+#[test]
+fn initialize_rejects_second_call() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.default_royalty(), (admin, 0));
+}
+
+#[test]
+fn transfer_reports_missing_token_and_wrong_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    assert_eq!(
+        client.try_transfer(&stranger, &owner, &token_id),
+        Err(Ok(Error::NotTokenOwner))
+    );
+    assert_eq!(
+        client.try_transfer(&owner, &stranger, &99),
+        Err(Ok(Error::TokenNotFound))
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+}
+
+#[test]
+fn lookups_report_missing_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+
+    assert_eq!(client.try_owner_of(&1), Err(Ok(Error::TokenNotFound)));
+    assert_eq!(client.try_token_uri(&1), Err(Ok(Error::TokenNotFound)));
+
+    let spender = Address::generate(&env);
+    assert_eq!(
+        client.try_approve(&spender, &1),
+        Err(Ok(Error::TokenNotFound))
+    );
+    assert_eq!(
+        client.try_revoke_approval(&1),
+        Err(Ok(Error::TokenNotFound))
+    );
+    assert_eq!(
+        client.try_transfer_from(&spender, &spender, &Address::generate(&env), &1),
+        Err(Ok(Error::TokenNotFound))
+    );
+}
+
+#[test]
+fn operator_approval_can_be_granted_and_revoked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    let operator_can_manage = || {
+        env.as_contract(&client.address, || {
+            PlayerCardToken::is_approved_or_owner(env.clone(), operator.clone(), token_id)
+                == Ok(true)
+        })
+    };
+
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    assert!(!operator_can_manage());
+
+    client.set_approval_for_all(&owner, &operator, &true);
+    assert!(client.is_approved_for_all(&owner, &operator));
+    assert!(operator_can_manage());
+
+    // Approval is per owner, not per operator
+    assert!(!client.is_approved_for_all(&operator, &owner));
+
+    client.set_approval_for_all(&owner, &operator, &false);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    assert!(!operator_can_manage());
+}
+
+#[test]
+fn restricted_approvals_only_accept_allowlisted_operators() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    let unknown = Address::generate(&env);
+
+    // Unrestricted by default, so any operator can be approved
+    assert!(!client.is_operator_restricted());
+    client.set_approval_for_all(&owner, &unknown, &true);
+    assert!(client.is_approved_for_all(&owner, &unknown));
+
+    assert_eq!(
+        client.try_set_operator_restriction(&owner, &true),
+        Err(Ok(Error::NotAdmin))
+    );
+    client.set_operator_restriction(&admin, &true);
+    client.allow_operator(&admin, &marketplace);
+    assert!(client.is_operator_allowed(&marketplace));
+
+    client.set_approval_for_all(&owner, &marketplace, &true);
+    assert!(client.is_approved_for_all(&owner, &marketplace));
+    assert_eq!(
+        client.try_set_approval_for_all(&owner, &Address::generate(&env), &true),
+        Err(Ok(Error::OperatorNotAllowed))
+    );
+
+    // Revoking an operator approved before the restriction still works
+    client.set_approval_for_all(&owner, &unknown, &false);
+    assert!(!client.is_approved_for_all(&owner, &unknown));
+
+    client.disallow_operator(&admin, &marketplace);
+    assert_eq!(
+        client.try_set_approval_for_all(&owner, &marketplace, &true),
+        Err(Ok(Error::OperatorNotAllowed))
+    );
+
+    client.set_operator_restriction(&admin, &false);
+    client.set_approval_for_all(&owner, &unknown, &true);
+    assert!(client.is_approved_for_all(&owner, &unknown));
+}
+
+#[test]
+fn approved_address_can_transfer_from_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.approve(&spender, &token_id);
+    assert_eq!(client.get_approved(&token_id), Some(spender.clone()));
+
+    client.transfer_from(&spender, &owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+    // Approval does not survive the transfer
+    assert_eq!(client.get_approved(&token_id), None);
+}
+
+#[test]
+fn approve_and_revoke_publish_approval_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    let approval = |approved: Option<Address>| {
+        (
+            client.address.clone(),
+            (common::NFT_APPROVAL_EVENT,).into_val(&env),
+            common::NFTApprovalEvent {
+                token_id: U256::from_u128(&env, token_id as u128),
+                owner: owner.clone(),
+                approved,
+                nft_contract: client.address.clone(),
+                timestamp: env.ledger().timestamp(),
+            }
+            .into_val(&env),
+        )
+    };
+
+    client.approve(&spender, &token_id);
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![&env, approval(Some(spender.clone()))]
+    );
+
+    client.revoke_approval(&token_id);
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![&env, approval(None)]
+    );
+    assert_eq!(client.get_approved(&token_id), None);
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &spender, &token_id),
+        Err(Ok(Error::NotApproved))
+    );
+}
+
+#[test]
+fn owner_transfer_clears_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.approve(&spender, &token_id);
+    client.transfer(&owner, &buyer, &token_id);
+
+    assert_eq!(client.get_approved(&token_id), None);
+    assert_eq!(
+        client.try_transfer_from(&spender, &buyer, &spender, &token_id),
+        Err(Ok(Error::NotApproved))
+    );
+}
+
+#[test]
+fn unapproved_address_cannot_transfer_from_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    assert_eq!(
+        client.try_transfer_from(&stranger, &owner, &stranger, &token_id),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+}
+
+#[test]
+fn operator_can_transfer_on_owners_behalf() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    client.set_approval_for_all(&owner, &marketplace, &true);
+    client.transfer_from(&marketplace, &owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+
+    // The operator has no say over the buyer's cards
+    assert!(client
+        .try_transfer_from(&marketplace, &buyer, &owner, &token_id)
+        .is_err());
+}
+
+#[test]
+fn mint_batch_assigns_consecutive_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    let token_ids = client.mint_batch(
+        &vec![&env, first.clone(), second.clone(), third.clone()],
+        &vec![
+            &env,
+            String::from_str(&env, "ipfs://a"),
+            String::from_str(&env, "ipfs://b"),
+            String::from_str(&env, "ipfs://c"),
+        ],
+    );
+
+    assert_eq!(token_ids, vec![&env, 1, 2, 3]);
+    assert_eq!(client.owner_of(&1), first);
+    assert_eq!(client.owner_of(&2), second);
+    assert_eq!(client.owner_of(&3), third);
+    assert_eq!(client.token_uri(&2), String::from_str(&env, "ipfs://b"));
+    assert_eq!(client.total_supply(), 3);
+}
+
+#[test]
+fn mint_batch_rejects_mismatched_lengths() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+
+    assert_eq!(
+        client.try_mint_batch(
+            &vec![&env, Address::generate(&env), Address::generate(&env)],
+            &vec![&env, String::from_str(&env, "ipfs://a")],
+        ),
+        Err(Ok(Error::InvalidRecipient))
+    );
+    assert_eq!(client.total_supply(), 0);
+}
+
+#[test]
+fn genesis_window_lets_admin_mint_past_max_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+
+    assert_eq!(
+        client.try_set_genesis_window(&admin, &1_000, &4),
+        Err(Ok(Error::GenesisClosed))
+    );
+    client.set_max_supply(&admin, &Some(2));
+    client.set_genesis_window(&admin, &2_000, &4);
+    assert_eq!(client.genesis_window(), Some((2_000, 4)));
+
+    // During the window admin mints may go past max_supply, up to the genesis cap
+    for seed in 1..=4 {
+        mint_card(&env, &client, seed, &owner);
+    }
+    assert_eq!(client.total_supply(), 4);
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[5u8; 32]),
+            &owner,
+            &String::from_str(&env, "ipfs://card"),
+            &None,
+        ),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+
+    // Once it closes, the normal cap applies again
+    env.ledger().set_timestamp(2_000);
+    client.burn(&owner, &1);
+    client.burn(&owner, &2);
+    client.burn(&owner, &3);
+    mint_card(&env, &client, 6, &owner);
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[7u8; 32]),
+            &owner,
+            &String::from_str(&env, "ipfs://card"),
+            &None,
+        ),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+
+    // The window can't be reopened once cards exist
+    assert_eq!(
+        client.try_set_genesis_window(&admin, &5_000, &10),
+        Err(Ok(Error::GenesisClosed))
+    );
+}
+
+#[test]
+fn max_supply_caps_minting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    mint_card(&env, &client, 1, &owner);
+
+    assert_eq!(
+        client.try_set_max_supply(&owner, &Some(3)),
+        Err(Ok(Error::NotAdmin))
+    );
+    assert_eq!(
+        client.try_set_max_supply(&admin, &Some(0)),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+    client.set_max_supply(&admin, &Some(3));
+    assert_eq!(client.max_supply(), Some(3));
+
+    let uris = vec![
+        &env,
+        String::from_str(&env, "ipfs://a"),
+        String::from_str(&env, "ipfs://b"),
+    ];
+    assert_eq!(
+        client.try_mint_batch(
+            &vec![&env, owner.clone(), owner.clone(), owner.clone()],
+            &vec![
+                &env,
+                String::from_str(&env, "ipfs://a"),
+                String::from_str(&env, "ipfs://b"),
+                String::from_str(&env, "ipfs://c"),
+            ],
+        ),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+    client.mint_batch(&vec![&env, owner.clone(), owner.clone()], &uris);
+    assert_eq!(client.total_supply(), 3);
+
+    assert_eq!(
+        client.try_mint(
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &owner,
+            &String::from_str(&env, "ipfs://card"),
+            &None,
+        ),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+
+    // Burning frees a slot under the cap
+    client.burn(&owner, &1);
+    mint_card(&env, &client, 3, &owner);
+    assert_eq!(client.total_supply(), 3);
+}
+
+#[test]
+fn uncapped_collection_keeps_minting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+
+    client.set_max_supply(&admin, &Some(1));
+    mint_card(&env, &client, 1, &owner);
+    client.set_max_supply(&admin, &None);
+    assert_eq!(client.max_supply(), None);
+
+    for seed in 2..6u8 {
+        mint_card(&env, &client, seed, &owner);
+    }
+    assert_eq!(client.total_supply(), 5);
+}
+
+#[test]
+fn locked_card_cannot_be_transferred() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    assert_eq!(
+        client.try_lock_token(&owner, &token_id),
+        Err(Ok(Error::NotAdmin))
+    );
+    assert_eq!(
+        client.try_lock_token(&admin, &99),
+        Err(Ok(Error::TokenNotFound))
+    );
+
+    client.lock_token(&admin, &token_id);
+    assert!(client.is_locked(&token_id));
+    assert_eq!(
+        client.try_transfer(&owner, &recipient, &token_id),
+        Err(Ok(Error::TokenLocked))
+    );
+    client.set_approval_for_all(&owner, &admin, &true);
+    assert_eq!(
+        client.try_transfer_from(&admin, &owner, &recipient, &token_id),
+        Err(Ok(Error::TokenLocked))
+    );
+
+    client.unlock_token(&admin, &token_id);
+    assert!(!client.is_locked(&token_id));
+    client.transfer(&owner, &recipient, &token_id);
+    assert_eq!(client.owner_of(&token_id), recipient);
+}
+
+#[test]
+fn cards_upgrade_until_max_level() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+    assert_eq!(client.get_card_level(&token_id), 1);
+    assert_eq!(client.max_card_level(), DEFAULT_MAX_CARD_LEVEL);
+
+    client.set_max_card_level(&admin, &3);
+    assert_eq!(client.upgrade_card(&admin, &token_id), 2);
+    assert_eq!(client.upgrade_card(&admin, &token_id), 3);
+
+    let event = NFTUpgradeEvent {
+        token_id: U256::from_u128(&env, token_id as u128),
+        owner,
+        level: 3,
+        nft_contract: client.address.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        contract_events(&env, &client.address),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (NFT_UPGRADE_EVENT,).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(
+        client.try_upgrade_card(&admin, &token_id),
+        Err(Ok(Error::MaxLevelReached))
+    );
+    assert_eq!(client.get_card_level(&token_id), 3);
+}
+
+#[test]
+fn upgrade_card_requires_admin_and_existing_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+
+    assert_eq!(
+        client.try_upgrade_card(&owner, &token_id),
+        Err(Ok(Error::NotAdmin))
+    );
+    assert_eq!(
+        client.try_upgrade_card(&admin, &99),
+        Err(Ok(Error::TokenNotFound))
+    );
+    assert_eq!(client.get_card_level(&token_id), 1);
+}
+
+#[test]
+fn admin_can_update_token_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let token_id = mint_card(&env, &client, 1, &Address::generate(&env));
+    assert!(client.token_metadata(&token_id).is_empty());
+
+    let mut metadata = Map::new(&env);
+    metadata.set(Symbol::new(&env, "position"), String::from_str(&env, "FW"));
+    metadata.set(Symbol::new(&env, "rating"), String::from_str(&env, "91"));
+    client.set_token_metadata(&admin, &token_id, &metadata);
+    assert_eq!(client.token_metadata(&token_id), metadata);
+
+    // Series attributes recorded at mint are readable too
+    let series_id = client.mint_series(
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &admin,
+        &String::from_str(&env, "ipfs://card"),
+        &Symbol::new(&env, "S2024"),
+        &None,
+    );
+    assert_eq!(
+        client
+            .token_metadata(&series_id)
+            .get(Symbol::new(&env, "edition")),
+        Some(String::from_str(&env, "1"))
+    );
+}
+
+#[test]
+fn set_token_metadata_rejects_unknown_token_and_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let owner = Address::generate(&env);
+    let token_id = mint_card(&env, &client, 1, &owner);
+    let metadata = Map::new(&env);
+
+    assert_eq!(
+        client.try_set_token_metadata(&admin, &99, &metadata),
+        Err(Ok(Error::TokenNotFound))
+    );
+    assert_eq!(
+        client.try_set_token_metadata(&owner, &token_id, &metadata),
+        Err(Ok(Error::NotAdmin))
+    );
+
+    client.set_metadata_limits(&admin, &1, &DEFAULT_MAX_URI_LEN);
+    let mut oversized = Map::new(&env);
+    oversized.set(Symbol::new(&env, "a"), String::from_str(&env, "1"));
+    oversized.set(Symbol::new(&env, "b"), String::from_str(&env, "2"));
+    assert_eq!(
+        client.try_set_token_metadata(&admin, &token_id, &oversized),
+        Err(Ok(Error::MetadataTooLarge))
+    );
+}
+
+#[test]
+fn admin_handoff_completes_on_accept() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let successor = Address::generate(&env);
+    let receiver = Address::generate(&env);
+
+    client.propose_admin(&admin, &successor);
+    assert_eq!(client.pending_admin(), Some(successor.clone()));
+    // Nothing changes until the nominee accepts
+    assert_eq!(client.collection_config().admin, admin);
+
+    client.accept_admin(&successor);
+    assert_eq!(client.collection_config().admin, successor);
+    assert_eq!(client.pending_admin(), None);
+
+    client.set_default_royalty(&successor, &receiver, &100);
+    assert_eq!(
+        client.try_set_default_royalty(&admin, &receiver, &100),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn only_nominee_can_accept_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_test(&env);
+    let successor = Address::generate(&env);
+    let intruder = Address::generate(&env);
+
+    assert_eq!(client.try_accept_admin(&intruder), Err(Ok(Error::NotAdmin)));
+
+    client.propose_admin(&admin, &successor);
+    assert_eq!(client.try_accept_admin(&intruder), Err(Ok(Error::NotAdmin)));
+    assert_eq!(
+        client.try_propose_admin(&intruder, &intruder),
+        Err(Ok(Error::NotAdmin))
+    );
+    assert_eq!(client.collection_config().admin, admin);
+}
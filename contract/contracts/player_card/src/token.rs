@@ -1,13 +1,14 @@
-use soroban_sdk::{Address, Env, String, Vec};
+use common::{NFTApprovalEvent, NFT_APPROVAL_EVENT};
+use soroban_sdk::{Address, Env, String, Vec, U256};
 
 use crate::{storage, Error};
 
 pub trait TokenInterface {
-    fn initialize(env: Env, admin: Address);
-    fn mint(env: Env, to: Address, token_uri: String) -> u64;
-    fn transfer(env: Env, from: Address, to: Address, token_id: u64);
-    fn owner_of(env: Env, token_id: u64) -> Address;
-    fn token_uri(env: Env, token_id: u64) -> String;
+    fn initialize(env: Env, admin: Address) -> Result<(), Error>;
+    fn mint(env: Env, to: Address, token_uri: String) -> Result<u64, Error>;
+    fn transfer(env: Env, from: Address, to: Address, token_id: u64) -> Result<(), Error>;
+    fn owner_of(env: Env, token_id: u64) -> Result<Address, Error>;
+    fn token_uri(env: Env, token_id: u64) -> Result<String, Error>;
     fn total_supply(env: Env) -> u64;
     fn tokens_of_owner(env: Env, owner: Address) -> Vec<u64>;
 }
@@ -15,33 +16,54 @@ pub trait TokenInterface {
 pub struct PlayerCardToken;
 
 impl PlayerCardToken {
-    pub fn burn(_env: Env, _from: Address, _token_id: u64) -> Result<(), Error> {
-        Err(Error::BurnDisabled)
+    pub fn approve(env: Env, approved: Address, token_id: u64) -> Result<(), Error> {
+        let owner = storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)?;
+        owner.require_auth();
+
+        storage::set_approved(&env, token_id, &approved);
+        Self::publish_approval(&env, owner, Some(approved), token_id);
+        Ok(())
     }
 
-    pub fn approve(env: Env, approved: Address, token_id: u64) {
-        let owner = storage::get_owner(&env, token_id);
+    /// Withdraw a card's approval, e.g. when a marketplace listing is cancelled
+    pub fn revoke_approval(env: Env, token_id: u64) -> Result<(), Error> {
+        let owner = storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)?;
         owner.require_auth();
 
-        let key = ("approval", token_id);
-        env.storage().instance().set(&key, &approved);
+        Self::clear_approval(&env, token_id);
+        Self::publish_approval(&env, owner, None, token_id);
+        Ok(())
+    }
+
+    fn publish_approval(env: &Env, owner: Address, approved: Option<Address>, token_id: u64) {
+        let event = NFTApprovalEvent {
+            token_id: U256::from_u128(env, token_id as u128),
+            owner,
+            approved,
+            nft_contract: env.current_contract_address(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((NFT_APPROVAL_EVENT,), event);
+    }
+
+    pub fn clear_approval(env: &Env, token_id: u64) {
+        storage::remove_approved(env, token_id);
     }
 
     pub fn get_approved(env: Env, token_id: u64) -> Option<Address> {
-        let key = ("approval", token_id);
-        env.storage().instance().get(&key)
+        storage::get_approved(&env, token_id)
     }
 
-    pub fn is_approved_or_owner(env: Env, spender: Address, token_id: u64) -> bool {
-        let owner = storage::get_owner(&env, token_id);
-        if spender == owner {
-            return true;
+    pub fn is_approved_or_owner(env: Env, spender: Address, token_id: u64) -> Result<bool, Error> {
+        let owner = storage::try_get_owner(&env, token_id).ok_or(Error::TokenNotFound)?;
+        if spender == owner || storage::is_approved_for_all(&env, &owner, &spender) {
+            return Ok(true);
         }
 
         if let Some(approved) = Self::get_approved(env, token_id) {
-            return spender == approved;
+            return Ok(spender == approved);
         }
 
-        false
+        Ok(false)
     }
 }
@@ -1,8 +1,8 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, U256};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, IntoVal, Symbol, Val, U256};
 
-use common::ContractError;
+use common::{extend_persistent, ContractError};
 
 #[contracttype]
 #[derive(Clone)]
@@ -21,6 +21,7 @@ enum DataKey {
     BackendSigner,
     BalanceLedgerContract,
     Settled(U256),
+    OutcomeCount(Symbol),
 }
 
 #[contract]
@@ -47,8 +48,16 @@ impl SettlementContract {
         env.storage().persistent().has(&DataKey::Settled(bet_id))
     }
 
+    /// Number of bets settled with the given outcome (WIN, LOSS, DRAW or PUSH)
+    pub fn settled_count(env: Env, outcome: Symbol) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OutcomeCount(outcome))
+            .unwrap_or(0)
+    }
+
     /// Settle a bet. Caller must be backend signer (oracle/admin).
-    /// Supports WIN, LOSS, DRAW (refund).
+    /// Supports WIN, LOSS, DRAW (refund) and PUSH (per-bet refund, no fee).
     pub fn settle_bet(
         env: Env,
         bet_id: U256,
@@ -73,38 +82,40 @@ impl SettlementContract {
         let win_sym = Symbol::short("WIN");
         let loss_sym = Symbol::short("LOSS");
         let draw_sym = Symbol::short("DRAW");
+        let push_sym = Symbol::short("PUSH");
 
         // Perform atomic fund updates by invoking balance ledger contract methods.
         if settlement_type == win_sym {
             // Winner must be provided
-            let winner_addr = winner.ok_or(ContractError::InvalidBet)?;
+            let winner_addr = winner.clone().ok_or(ContractError::InvalidBet)?;
 
             // Deduct locked funds from bettor
-            env.invoke_contract(
+            env.invoke_contract::<Val>(
                 &bal_contract,
                 &Symbol::new(&env, "apply_delta"),
-                (bettor.clone(), 0i128, -bet_amount),
+                (bettor.clone(), 0i128, -bet_amount).into_val(&env),
             );
 
             // Credit payout to winner withdrawable
-            env.invoke_contract(
+            env.invoke_contract::<Val>(
                 &bal_contract,
                 &Symbol::new(&env, "apply_delta"),
-                (winner_addr.clone(), payout, 0i128),
+                (winner_addr.clone(), payout, 0i128).into_val(&env),
             );
         } else if settlement_type == loss_sym {
             // Remove locked funds from bettor (platform keeps funds)
-            env.invoke_contract(
+            env.invoke_contract::<Val>(
                 &bal_contract,
                 &Symbol::new(&env, "apply_delta"),
-                (bettor.clone(), 0i128, -bet_amount),
+                (bettor.clone(), 0i128, -bet_amount).into_val(&env),
             );
-        } else if settlement_type == draw_sym {
-            // Refund: move locked funds back to withdrawable
-            env.invoke_contract(
+        } else if settlement_type == draw_sym || settlement_type == push_sym {
+            // Refund: move locked funds back to withdrawable. A push is neither a
+            // win nor a loss, so the full stake is returned without a fee.
+            env.invoke_contract::<Val>(
                 &bal_contract,
                 &Symbol::new(&env, "apply_delta"),
-                (bettor.clone(), bet_amount, -bet_amount),
+                (bettor.clone(), bet_amount, -bet_amount).into_val(&env),
             );
         } else {
             return Err(ContractError::InvalidStatus);
@@ -121,6 +132,17 @@ impl SettlementContract {
         };
         storage.set(&DataKey::Settled(bet_id), &record);
 
+        let count_key = DataKey::OutcomeCount(settlement_type.clone());
+        let count: u32 = storage.get(&count_key).unwrap_or(0);
+        let count = count
+            .checked_add(1)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        storage.set(&count_key, &count);
+        extend_persistent(&env, &count_key);
+
+        env.events()
+            .publish((Symbol::new(&env, "bet_settled"), settlement_type), record);
+
         Ok(())
     }
 }
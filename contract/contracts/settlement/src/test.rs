@@ -512,3 +512,58 @@ fn test_settle_win_loss_draw() {
     assert_eq!(after_refund.withdrawable, 500);
     assert_eq!(after_refund.locked, 0);
 }
+
+#[test]
+fn test_settle_push_refunds_stake() {
+    use soroban_sdk::{testutils::Address as _, Address};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let backend = Address::generate(&env);
+
+    let bl_contract_id = env.register(balance_ledger::BalanceLedgerContract, ());
+    let bl_client = balance_ledger::BalanceLedgerContractClient::new(&env, &bl_contract_id);
+    bl_client.initialize(&backend);
+
+    let st_contract_id = env.register(SettlementContract, ());
+    let st_client = SettlementContractClient::new(&env, &st_contract_id);
+    let bl_addr = Address::Contract(bl_contract_id.clone());
+    st_client.initialize(&backend, &bl_addr);
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    let pushed = Address::generate(&env);
+
+    for bettor in [&winner, &loser, &pushed] {
+        bl_client.set_balance(bettor, &1_000, &0);
+        bl_client.lock_funds(bettor, &100);
+    }
+
+    let win_sym = soroban_sdk::Symbol::short("WIN");
+    let loss_sym = soroban_sdk::Symbol::short("LOSS");
+    let push_sym = soroban_sdk::Symbol::short("PUSH");
+
+    st_client.settle_bet(
+        &U256::from_u64(1),
+        &winner,
+        &Some(winner.clone()),
+        &100,
+        &200,
+        &win_sym,
+    );
+    st_client.settle_bet(&U256::from_u64(2), &loser, &None, &100, &0, &loss_sym);
+    st_client.settle_bet(&U256::from_u64(3), &pushed, &None, &100, &0, &push_sym);
+
+    assert_eq!(bl_client.get_balance(&winner).withdrawable, 1_100);
+    assert_eq!(bl_client.get_balance(&loser).withdrawable, 900);
+
+    // Pushed stake is returned in full
+    let pushed_balance = bl_client.get_balance(&pushed);
+    assert_eq!(pushed_balance.withdrawable, 1_000);
+    assert_eq!(pushed_balance.locked, 0);
+
+    assert_eq!(st_client.settled_count(&win_sym), 1);
+    assert_eq!(st_client.settled_count(&loss_sym), 1);
+    assert_eq!(st_client.settled_count(&push_sym), 1);
+}
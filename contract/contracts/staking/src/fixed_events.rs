@@ -1,4 +1,4 @@
-use soroban_sdk::{symbol_short, Env, Address};
+use soroban_sdk::{symbol_short, Env, Address, Symbol};
 
 pub fn fixed_stake_event(env: &Env, user: &Address, amount: i128, duration: u64) {
     env.events().publish(
@@ -9,7 +9,7 @@ pub fn fixed_stake_event(env: &Env, user: &Address, amount: i128, duration: u64)
 
 pub fn fixed_unstake_event(env: &Env, user: &Address, reward: i128) {
     env.events().publish(
-        (symbol_short!("fx_unstake"), user),
+        (Symbol::new(env, "fx_unstake"), user),
         reward,
     );
 }
\ No newline at end of file
@@ -1,5 +1,3 @@
-#![no_std]
-
 use soroban_sdk::{
     Env, Address, panic_with_error
 };
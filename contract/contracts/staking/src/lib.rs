@@ -2,11 +2,66 @@
 
 use common::errors::ContractError;
 use common::events::{create_stake_event, create_unstake_event, STAKE_EVENT, UNSTAKE_EVENT};
+use common::rewards::accrued;
 use soroban_sdk::{contract, contractimpl, token, Address, Env, U256};
 
 pub mod storage;
 use storage::{DataKey, StakeData};
 
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 3600;
+
+// Simple interest on `principal` at `rate_bps` APR between `from` and `now`:
+// principal * rate_bps / 10000 * elapsed / seconds_per_year
+fn reward_for(principal: i128, rate_bps: i128, from: u64, now: u64) -> Result<i128, ContractError> {
+    let scaled_rate = principal
+        .checked_mul(rate_bps)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    Ok(accrued(scaled_rate, from, now)? / (10_000 * SECONDS_PER_YEAR))
+}
+
+// Rewards a position has earned since they were last compounded into it
+// (or since it was opened). This is the only place rewards are computed, so
+// compounding and unstaking can never pay the same period twice.
+fn position_reward(
+    env: &Env,
+    user: &Address,
+    stake_id: &U256,
+    stake_data: &StakeData,
+    rate_bps: i128,
+    now: u64,
+) -> Result<i128, ContractError> {
+    let from: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RewardFrom(user.clone(), stake_id.clone()))
+        .unwrap_or(stake_data.timestamp);
+    reward_for(stake_data.amount, rate_bps, from, now)
+}
+
+fn reward_rate(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::RewardRate)
+        .unwrap_or(0)
+}
+
+// Take `amount` of rewards out of the admin-funded pool, so rewards are never
+// paid from other stakers' principal.
+fn draw_reward_pool(env: &Env, amount: i128) -> Result<(), ContractError> {
+    let pool: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::RewardPool)
+        .unwrap_or(0);
+    if amount > pool {
+        return Err(ContractError::InsufficientBalance);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::RewardPool, &(pool - amount));
+    Ok(())
+}
+
 // Helper to keep per-user active staking duration up to date whenever their
 // total staked amount changes between zero and non-zero.
 fn update_user_active_duration_on_change(
@@ -104,7 +159,14 @@ impl StakingContract {
         Ok(())
     }
 
-    pub fn stake(env: Env, user: Address, amount: i128) -> Result<U256, ContractError> {
+    /// Escrows `amount` of `token_address` and returns the new stake id.
+    /// `token_address` must be the staking token configured at initialization.
+    pub fn stake(
+        env: Env,
+        user: Address,
+        token_address: Address,
+        amount: i128,
+    ) -> Result<U256, ContractError> {
         user.require_auth();
 
         let staking_token: Address = env
@@ -112,6 +174,9 @@ impl StakingContract {
             .instance()
             .get(&DataKey::StakingToken)
             .ok_or(ContractError::NotInitialized)?;
+        if token_address != staking_token {
+            return Err(ContractError::InvalidToken);
+        }
         let min_stake: i128 = env.storage().instance().get(&DataKey::MinStake).unwrap();
 
         if amount < min_stake {
@@ -140,28 +205,33 @@ impl StakingContract {
         // Update total stake and per-user active duration
         let total_key = DataKey::TotalStake(user.clone());
         let current_total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
-        let new_total = current_total + amount;
+        let new_total = current_total
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
 
         update_user_active_duration_on_change(&env, &user, current_total, new_total);
 
         env.storage().persistent().set(&total_key, &new_total);
 
         // Emit Event
-        let mut event = create_stake_event(
+        let event = create_stake_event(
+            &env,
             user.clone(),
             amount,
             staking_token,
             env.current_contract_address(),
             stake_id.clone(),
         );
-        event.timestamp = timestamp;
         #[allow(deprecated)] // keep (topic, user) format for indexer compatibility
         env.events().publish((STAKE_EVENT, user.clone()), event);
 
         Ok(stake_id)
     }
 
-    pub fn unstake(env: Env, user: Address, stake_id: U256) -> Result<(), ContractError> {
+    /// Releases a stake once its cooldown has elapsed and returns the amount
+    /// paid out (principal plus rewards). Fails with `InsufficientBalance` if
+    /// the reward pool can't cover the rewards.
+    pub fn unstake(env: Env, user: Address, stake_id: U256) -> Result<i128, ContractError> {
         user.require_auth();
 
         let staking_token: Address = env
@@ -183,12 +253,30 @@ impl StakingContract {
             .ok_or(ContractError::StakeNotFound)?;
 
         let current_time = env.ledger().timestamp();
-        if current_time < stake_data.timestamp + cooldown_period {
+        if current_time < stake_data.timestamp.saturating_add(cooldown_period) {
             return Err(ContractError::CooldownNotMet);
         }
 
+        // Rewards not yet compounded into the position, up to now
+        let rewards = position_reward(
+            &env,
+            &user,
+            &stake_id,
+            &stake_data,
+            reward_rate(&env),
+            current_time,
+        )?;
+        let payout = stake_data
+            .amount
+            .checked_add(rewards)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        draw_reward_pool(&env, rewards)?;
+
         // Remove the stake
         env.storage().persistent().remove(&stake_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RewardFrom(user.clone(), stake_id.clone()));
 
         // Update total stake and per-user active duration
         let total_key = DataKey::TotalStake(user.clone());
@@ -205,22 +293,22 @@ impl StakingContract {
 
         // Transfer tokens back to user
         let token_client = token::Client::new(&env, &staking_token);
-        token_client.transfer(&env.current_contract_address(), &user, &stake_data.amount);
+        token_client.transfer(&env.current_contract_address(), &user, &payout);
 
         // Emit Event
-        let mut event = create_unstake_event(
+        let event = create_unstake_event(
+            &env,
             user.clone(),
             stake_data.amount,
             staking_token,
             env.current_contract_address(),
             stake_id,
-            0, // Rewards are not implemented in this version, hardcode 0
+            rewards,
         );
-        event.timestamp = current_time;
         #[allow(deprecated)] // keep (topic, user) format for indexer compatibility
         env.events().publish((UNSTAKE_EVENT, user.clone()), event);
 
-        Ok(())
+        Ok(payout)
     }
 
     pub fn get_total_stake(env: Env, user: Address) -> i128 {
@@ -290,84 +378,149 @@ impl StakingContract {
         if admin != stored_admin {
             return Err(ContractError::Unauthorized);
         }
-        env.storage().instance().set(&DataKey::RewardRate, &rate_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardRate, &rate_bps);
         Ok(())
     }
 
-    /// Accrue pending rewards for a user based on elapsed time and their total stake.
-    /// Called internally before any stake/unstake/compound operation.
-    fn accrue_rewards(env: &Env, user: &Address) {
-        let total_key = DataKey::TotalStake(user.clone());
-        let total_stake: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
-        if total_stake == 0 {
-            return;
-        }
-
-        let rate_bps: i128 = env.storage().instance().get(&DataKey::RewardRate).unwrap_or(0);
-        if rate_bps == 0 {
-            return;
+    /// Move `amount` of the staking token from `admin` into the reward pool
+    /// that unstaking and compounding pay rewards from.
+    pub fn fund_rewards(env: Env, admin: Address, amount: i128) -> Result<(), ContractError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(ContractError::Unauthorized);
         }
-
-        let now = env.ledger().timestamp();
-        let last_time_key = DataKey::LastRewardTime(user.clone());
-        let last_time: u64 = env.storage().persistent().get(&last_time_key).unwrap_or(now);
-
-        let elapsed = now.saturating_sub(last_time) as i128;
-        if elapsed == 0 {
-            return;
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
         }
 
-        // reward = principal * rate_bps / 10000 * elapsed / seconds_per_year
-        let seconds_per_year: i128 = 365 * 24 * 3600;
-        let reward = total_stake * rate_bps * elapsed / (10_000 * seconds_per_year);
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::StakingToken)
+            .ok_or(ContractError::NotInitialized)?;
+        let pool: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RewardPool)
+            .unwrap_or(0);
+        let new_pool = pool
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardPool, &new_pool);
 
-        if reward > 0 {
-            let pending_key = DataKey::PendingRewards(user.clone());
-            let pending: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
-            env.storage().persistent().set(&pending_key, &(pending + reward));
-        }
+        token::Client::new(&env, &staking_token).transfer(
+            &admin,
+            env.current_contract_address(),
+            &amount,
+        );
+        Ok(())
+    }
 
-        env.storage().persistent().set(&last_time_key, &now);
+    /// Tokens left in the reward pool.
+    pub fn get_reward_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardPool)
+            .unwrap_or(0)
     }
 
-    /// Compound pending rewards: add them to the user's total staked principal.
-    /// Tracked separately in CompoundedAmount for transparency.
+    /// Compound rewards earned so far into each of the user's open positions,
+    /// growing their principal. Unstaking then pays rewards only on the time
+    /// since, so no period is rewarded twice. The rewards come out of the
+    /// reward pool, which must cover them. Returns the amount compounded.
     pub fn compound_rewards(env: Env, user: Address) -> Result<i128, ContractError> {
         user.require_auth();
 
-        Self::accrue_rewards(&env, &user);
+        let rate_bps = reward_rate(&env);
+        let now = env.ledger().timestamp();
+        let nonce: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StakeNonce(user.clone()))
+            .unwrap_or(0);
 
-        let pending_key = DataKey::PendingRewards(user.clone());
-        let pending: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        let mut compounded_now: i128 = 0;
+        for id in 0..nonce {
+            let stake_id = U256::from_u32(&env, id);
+            let stake_key = DataKey::UserStake(user.clone(), stake_id.clone());
+            let Some(mut stake_data) = env.storage().persistent().get::<_, StakeData>(&stake_key)
+            else {
+                continue;
+            };
+
+            let reward = position_reward(&env, &user, &stake_id, &stake_data, rate_bps, now)?;
+            if reward <= 0 {
+                continue;
+            }
+            stake_data.amount = stake_data
+                .amount
+                .checked_add(reward)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            env.storage().persistent().set(&stake_key, &stake_data);
+            env.storage()
+                .persistent()
+                .set(&DataKey::RewardFrom(user.clone(), stake_id), &now);
+            compounded_now = compounded_now
+                .checked_add(reward)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
 
-        if pending <= 0 {
+        if compounded_now <= 0 {
             return Err(ContractError::NoRewardsToClaim);
         }
+        draw_reward_pool(&env, compounded_now)?;
 
-        // Add pending rewards to total stake (compounding)
+        // Add compounded rewards to total stake
         let total_key = DataKey::TotalStake(user.clone());
         let current_total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
-        let new_total = current_total + pending;
+        let new_total = current_total
+            .checked_add(compounded_now)
+            .ok_or(ContractError::ArithmeticOverflow)?;
         env.storage().persistent().set(&total_key, &new_total);
 
         // Track total compounded amount separately
         let compounded_key = DataKey::CompoundedAmount(user.clone());
         let prev_compounded: i128 = env.storage().persistent().get(&compounded_key).unwrap_or(0);
-        env.storage().persistent().set(&compounded_key, &(prev_compounded + pending));
-
-        // Clear pending rewards
-        env.storage().persistent().set(&pending_key, &0i128);
+        let compounded = prev_compounded
+            .checked_add(compounded_now)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        env.storage().persistent().set(&compounded_key, &compounded);
 
-        Ok(pending)
+        Ok(compounded_now)
     }
 
-    /// Get pending (uncompounded) rewards for a user.
-    pub fn get_pending_rewards(env: Env, user: Address) -> i128 {
-        Self::accrue_rewards(&env, &user);
-        env.storage()
+    /// Rewards earned across the user's open positions and not yet compounded,
+    /// i.e. what unstaking every position now would pay on top of principal.
+    pub fn get_pending_rewards(env: Env, user: Address) -> Result<i128, ContractError> {
+        let rate_bps = reward_rate(&env);
+        let now = env.ledger().timestamp();
+        let nonce: u32 = env
+            .storage()
             .persistent()
-            .get(&DataKey::PendingRewards(user))
-            .unwrap_or(0)
+            .get(&DataKey::StakeNonce(user.clone()))
+            .unwrap_or(0);
+
+        let mut pending: i128 = 0;
+        for id in 0..nonce {
+            let stake_id = U256::from_u32(&env, id);
+            let stake_key = DataKey::UserStake(user.clone(), stake_id.clone());
+            if let Some(stake_data) = env.storage().persistent().get::<_, StakeData>(&stake_key) {
+                let reward = position_reward(&env, &user, &stake_id, &stake_data, rate_bps, now)?;
+                pending = pending
+                    .checked_add(reward)
+                    .ok_or(ContractError::ArithmeticOverflow)?;
+            }
+        }
+        Ok(pending)
     }
 
     /// Get total amount added to principal via compounding.
@@ -382,10 +535,8 @@ impl StakingContract {
 #[cfg(test)]
 mod test;
 
-mod fixed_term;
-mod fixed_storage;
-mod fixed_types;
-mod fixed_errors;
-mod fixed_events;
-
-use fixed_term::FixedLockStaking;
+pub mod fixed_errors;
+pub mod fixed_events;
+pub mod fixed_storage;
+pub mod fixed_term;
+pub mod fixed_types;
@@ -14,14 +14,14 @@ pub enum DataKey {
     ActiveSince(Address),        // u64: Timestamp when user last became an active staker
     // Compounding
     RewardRate,                  // i128: Annual reward rate in basis points (e.g. 1200 = 12%)
-    PendingRewards(Address),     // i128: Accumulated pending rewards for a user
     CompoundedAmount(Address),   // i128: Total amount added to principal via compounding
-    LastRewardTime(Address),     // u64: Last time rewards were accrued for a user
+    RewardFrom(Address, U256),   // u64: When a position last had rewards compounded
+    RewardPool,                  // i128: Admin-funded tokens set aside to pay rewards
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StakeData {
     pub amount: i128,
-    pub timestamp: u64,
+    pub timestamp: u64, // Start of the stake; rewards accrue from here
 }
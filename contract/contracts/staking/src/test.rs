@@ -58,7 +58,7 @@ fn test_stake_and_unstake() {
     });
 
     // Stake
-    let stake_id = client.stake(&user, &amount);
+    let stake_id = client.stake(&user, &token_client.address, &amount);
 
     assert_eq!(token_client.balance(&user), 100_000_000 - amount);
     assert_eq!(token_client.balance(&client.address), amount);
@@ -77,7 +77,8 @@ fn test_stake_and_unstake() {
         li.timestamp = 100000 + 90000; // Passed 86400 cooldown
     });
 
-    client.unstake(&user, &stake_id);
+    let payout = client.unstake(&user, &stake_id);
+    assert_eq!(payout, amount);
     assert_eq!(client.get_total_stake(&user), 0);
     assert_eq!(token_client.balance(&user), 100_000_000); // Returned to original balance
     assert_eq!(token_client.balance(&client.address), 0);
@@ -92,7 +93,7 @@ fn test_below_min_stake() {
     client.initialize(&admin, &token_client.address, &min_stake, &cooldown_period);
 
     let amount = 500;
-    let res = client.try_stake(&user, &amount);
+    let res = client.try_stake(&user, &token_client.address, &amount);
     assert_eq!(res, Err(Ok(ContractError::BelowMinStake)));
 }
 
@@ -109,3 +110,176 @@ fn test_stake_not_found() {
     let res = client.try_unstake(&user, &fake_id);
     assert_eq!(res, Err(Ok(ContractError::StakeNotFound)));
 }
+
+#[test]
+fn test_stake_rejects_unconfigured_token() {
+    let (env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let res = client.try_stake(&user, &other_token, &5000);
+    assert_eq!(res, Err(Ok(ContractError::InvalidToken)));
+    assert_eq!(client.get_total_stake(&user), 0);
+}
+
+#[test]
+fn test_stake_round_trip_multiple_positions() {
+    let (env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let first = client.stake(&user, &token_client.address, &2000);
+    let second = client.stake(&user, &token_client.address, &3000);
+    assert_ne!(first, second);
+    assert_eq!(client.get_stake(&user, &first).amount, 2000);
+    assert_eq!(client.get_stake(&user, &second).timestamp, 1_000);
+    assert_eq!(client.get_total_stake(&user), 5000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 86400);
+    assert_eq!(client.unstake(&user, &first), 2000);
+    assert_eq!(client.get_total_stake(&user), 3000);
+    assert_eq!(
+        client.try_get_stake(&user, &first),
+        Err(Ok(ContractError::StakeNotFound))
+    );
+
+    assert_eq!(client.unstake(&user, &second), 3000);
+    assert_eq!(token_client.balance(&user), 100_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_unstake_pays_time_based_rewards() {
+    let (env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+    client.set_reward_rate(&admin, &1200); // 12% APR
+
+    // Fund the reward pool so it can pay rewards on top of principal
+    token::StellarAssetClient::new(&env, &token_client.address).mint(&admin, &1_000_000);
+    client.fund_rewards(&admin, &1_000_000);
+
+    let amount: i128 = 1_000_000;
+    env.ledger().set_timestamp(10_000);
+    let stake_id = client.stake(&user, &token_client.address, &amount);
+
+    let elapsed: u64 = 90 * 24 * 3600;
+    env.ledger().set_timestamp(10_000 + elapsed);
+
+    let expected_rewards = amount * 1200 * elapsed as i128 / (10_000 * 365 * 24 * 3600);
+    assert!(expected_rewards > 0);
+
+    let payout = client.unstake(&user, &stake_id);
+    assert_eq!(payout, amount + expected_rewards);
+    assert_eq!(token_client.balance(&user), 100_000_000 + expected_rewards);
+    assert_eq!(client.get_reward_pool(), 1_000_000 - expected_rewards);
+}
+
+#[test]
+fn test_rewards_are_not_paid_from_other_stakes() {
+    let (env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+    client.set_reward_rate(&admin, &1200); // 12% APR
+
+    let other = Address::generate(&env);
+    let asset = token::StellarAssetClient::new(&env, &token_client.address);
+    asset.mint(&other, &1_000_000);
+    asset.mint(&admin, &10);
+
+    env.ledger().set_timestamp(10_000);
+    let stake_id = client.stake(&user, &token_client.address, &1_000_000);
+    client.stake(&other, &token_client.address, &1_000_000);
+    env.ledger().set_timestamp(10_000 + 90 * 24 * 3600);
+
+    // Without a funded pool the other staker's principal is off limits
+    assert_eq!(
+        client.try_unstake(&user, &stake_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_compound_rewards(&user),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    client.fund_rewards(&admin, &10);
+    assert_eq!(
+        client.try_unstake(&user, &stake_id),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(client.get_reward_pool(), 10);
+    assert_eq!(token_client.balance(&client.address), 2_000_010);
+}
+
+#[test]
+fn test_only_admin_can_fund_rewards() {
+    let (_env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+
+    assert_eq!(
+        client.try_fund_rewards(&user, &1_000),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_fund_rewards(&admin, &0),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.get_reward_pool(), 0);
+}
+
+#[test]
+fn test_unstake_reports_reward_overflow() {
+    let (env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+    client.set_reward_rate(&admin, &1200);
+
+    let amount = i128::MAX / 1_000;
+    token::StellarAssetClient::new(&env, &token_client.address).mint(&user, &amount);
+
+    env.ledger().set_timestamp(10_000);
+    let stake_id = client.stake(&user, &token_client.address, &amount);
+    env.ledger().set_timestamp(10_000 + 86400);
+
+    let res = client.try_unstake(&user, &stake_id);
+    assert_eq!(res, Err(Ok(ContractError::ArithmeticOverflow)));
+    assert_eq!(client.get_total_stake(&user), amount);
+}
+
+#[test]
+fn test_compounded_rewards_are_not_paid_again_on_unstake() {
+    let (env, client, admin, user, token_client) = setup_test();
+    client.initialize(&admin, &token_client.address, &1000, &86400);
+    client.set_reward_rate(&admin, &1200); // 12% APR
+    token::StellarAssetClient::new(&env, &token_client.address).mint(&admin, &1_000_000);
+    client.fund_rewards(&admin, &1_000_000);
+
+    let amount: i128 = 1_000_000;
+    let period: u64 = 90 * 24 * 3600;
+    let reward = |principal: i128| principal * 1200 * period as i128 / (10_000 * 365 * 24 * 3600);
+
+    env.ledger().set_timestamp(10_000);
+    let stake_id = client.stake(&user, &token_client.address, &amount);
+
+    env.ledger().set_timestamp(10_000 + period);
+    let first = reward(amount);
+    assert_eq!(client.get_pending_rewards(&user), first);
+    assert_eq!(client.compound_rewards(&user), first);
+    assert_eq!(client.get_pending_rewards(&user), 0);
+    assert_eq!(client.get_stake(&user, &stake_id).amount, amount + first);
+    assert_eq!(client.get_total_stake(&user), amount + first);
+    assert_eq!(client.get_compounded_amount(&user), first);
+    assert_eq!(
+        client.try_compound_rewards(&user),
+        Err(Ok(ContractError::NoRewardsToClaim))
+    );
+
+    // Unstaking pays the compounded principal plus rewards for the second
+    // period only
+    env.ledger().set_timestamp(10_000 + 2 * period);
+    let second = reward(amount + first);
+    assert_eq!(client.get_pending_rewards(&user), second);
+    assert_eq!(client.unstake(&user, &stake_id), amount + first + second);
+    assert_eq!(client.get_total_stake(&user), 0);
+    assert_eq!(token_client.balance(&user), 100_000_000 + first + second);
+    assert_eq!(client.get_reward_pool(), 1_000_000 - first - second);
+}